@@ -3,6 +3,37 @@ import curses
 import typing
 
 stdscr = curses.initscr()
+# see CELL_SEPARATOR in src/lib.rs
+CELL_SEPARATOR = '\t'
+# see TABLE_GAP in src/bitmap.rs, in characters here
+TABLE_GAP = 1
+
+def _spread(cells: typing.List[str], width: int) -> str:
+    row = [' '] * width
+    last = len(cells) - 1
+    for (i, cell) in enumerate(cells):
+        if i == 0:
+            left = 0
+        elif i == last:
+            left = width - len(cell)
+        else:
+            left = width * i // last - len(cell) // 2
+        for (j, ch) in enumerate(cell):
+            if 0 <= left + j < width:
+                row[left + j] = ch
+    return ''.join(row)
+
+def _table(rows: typing.List[typing.List[str]], width: int) -> typing.List[str]:
+    '''Columns as wide as their widest cell, the first left aligned
+    and the rest right aligned, with the whole table centered'''
+    widths = [max(len(row[col]) for row in rows) for col in range(len(rows[0]))]
+    gap = ' ' * TABLE_GAP
+    ret = []
+    for row in rows:
+        cells = [row[0].ljust(widths[0])]
+        cells.extend(cell.rjust(w) for (cell, w) in zip(row[1:], widths[1:]))
+        ret.append(gap.join(cells).center(width))
+    return ret
 
 def render(lines: typing.List[str]):
     global stdscr
@@ -10,8 +41,23 @@ def render(lines: typing.List[str]):
     (_, width) = stdscr.getmaxyx()
     if width % 2 != 0:
         width = width - 1
-    for (i, line) in enumerate(lines):
-        line = line[1:].strip().center(width)
-        
+    rows = [[c.strip() for c in line[1:].split(CELL_SEPARATOR)] for line in lines]
+    out = []
+    i = 0
+    while i < len(rows):
+        cells = rows[i]
+        end = i
+        while end < len(rows) and len(rows[end]) == len(cells) > 1:
+            end += 1
+        if end - i > 1:
+            out.extend(_table(rows[i:end], width))
+            i = end
+            continue
+        i += 1
+        if len(cells) > 1:
+            out.append(_spread(cells, width))
+        else:
+            out.append(cells[0].center(width))
+    for (i, line) in enumerate(out):
         stdscr.addstr(i, 0, line)
-    stdscr.refresh()
\ No newline at end of file
+    stdscr.refresh()
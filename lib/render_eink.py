@@ -12,6 +12,10 @@ HOUR = 60 * MINUTE
 BACKGROUND_COLOR = 255
 FOREGROUND_COLOR = 1
 BORDER_WIDTH = 5
+# see CELL_SEPARATOR in src/lib.rs
+CELL_SEPARATOR = '\t'
+# see TABLE_GAP in src/bitmap.rs
+TABLE_GAP = 6
 
 try:
     font_path = os.environ['NBA_EINK_FONT']
@@ -44,10 +48,61 @@ def _gen_image_draw(display):
     draw.rectangle((0, 0, display.width, display.height), fill=BACKGROUND_COLOR)
     return (image, draw)
 
+def _draw_spread(draw, cells: List[str], font, top: int, screen_width: int):
+    '''The first cell against the left border, the last against the
+    right and any others centered at even steps between'''
+    last = len(cells) - 1
+    for (i, cell) in enumerate(cells):
+        if not cell:
+            continue
+        (width, _) = font.getsize(cell)
+        if i == 0:
+            left = BORDER_WIDTH
+        elif i == last:
+            left = screen_width - BORDER_WIDTH - width
+        else:
+            left = screen_width * i // last - width // 2
+        draw.text((left, top), cell, font=font, fill=FOREGROUND_COLOR)
+
+def _table_rows(rows, start: int) -> int:
+    '''How many rows from `start` have as many cells as the first'''
+    columns = len(rows[start][1])
+    end = start
+    while end < len(rows) and len(rows[end][1]) == columns > 1:
+        end += 1
+    return end - start
+
+def _draw_table(draw, rows, top: int, screen_width: int) -> int:
+    '''Columns as wide as their widest cell, the first left aligned
+    and the rest right aligned, with the whole table centered.
+    Returns the row below the table'''
+    widths = []
+    for (font, cells) in rows:
+        for (col, cell) in enumerate(cells):
+            (width, _) = font.getsize(cell)
+            if col < len(widths):
+                widths[col] = max(widths[col], width)
+            else:
+                widths.append(width)
+    total = sum(widths) + TABLE_GAP * (len(widths) - 1)
+    left = max((screen_width - total) // 2, BORDER_WIDTH)
+    for (font, cells) in rows:
+        column_left = left
+        for (col, cell) in enumerate(cells):
+            if cell:
+                (width, _) = font.getsize(cell)
+                cell_left = column_left if col == 0 else column_left + widths[col] - width
+                draw.text((cell_left, top), cell, font=font, fill=FOREGROUND_COLOR)
+            column_left += widths[col] + TABLE_GAP
+        (_, height) = font.getsize(''.join(cells))
+        top += height
+    return top
+
 def render(lines: List[str]):
     global display
     (image, draw) = _gen_image_draw(display)
     top = 5
+    rows = []
     for line in lines:
         char_ct = len(line)
         if char_ct == 0:
@@ -60,14 +115,27 @@ def render(lines: List[str]):
         else:
             # assume 0
             font = small_font
-        msg = line[1:]
-        (width, height) = font.getsize(msg)
-        if char_ct == 1:
+        rows.append((font, line[1:].split(CELL_SEPARATOR)))
+    i = 0
+    while i < len(rows):
+        (font, cells) = rows[i]
+        (width, height) = font.getsize(''.join(cells))
+        table = _table_rows(rows, i)
+        if table > 1:
+            top = _draw_table(draw, rows[i:i + table], top, display.width)
+            i += table
+            continue
+        i += 1
+        if cells == ['']:
             top += height + 5
             continue
+        if len(cells) > 1:
+            _draw_spread(draw, cells, font, top, display.width)
+            top += height
+            continue
         draw.text(
             ((display.width // 2) - (width // 2), top),
-            msg,
+            cells[0],
             font=font,
             fill=FOREGROUND_COLOR
         )
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::clock::GameClock;
+
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum Action {
@@ -81,23 +83,183 @@ impl Action {
         }
     }
 
-    pub fn clock(&self) -> &str {
+    pub fn clock(&self) -> GameClock {
+        match self {
+            Action::Period(info) => info.clock,
+            Action::JumpBall(info) => info.clock,
+            Action::Points(info) => info.clock,
+            Action::Rebound(info) => info.clock,
+            Action::Stoppage(info) => info.clock,
+            Action::Block(info) => info.clock,
+            Action::Turnover(info) => info.clock,
+            Action::Steal(info) => info.clock,
+            Action::Timeout(info) => info.clock,
+            Action::Substitution(info) => info.clock,
+            Action::Foul(info) => info.clock,
+            Action::FreeThrow(info) => info.clock,
+            Action::Violation(info) => info.clock,
+            Action::Game(info) => info.clock,
+            Action::Unknown(info) => info.clock,
+        }
+    }
+
+    pub fn kind(&self) -> ActionKind {
+        match self {
+            Action::Period(_) => ActionKind::Period,
+            Action::JumpBall(_) => ActionKind::JumpBall,
+            Action::Points(_) => ActionKind::Points,
+            Action::Rebound(_) => ActionKind::Rebound,
+            Action::Stoppage(_) => ActionKind::Stoppage,
+            Action::Block(_) => ActionKind::Block,
+            Action::Turnover(_) => ActionKind::Turnover,
+            Action::Steal(_) => ActionKind::Steal,
+            Action::Timeout(_) => ActionKind::Timeout,
+            Action::Substitution(_) => ActionKind::Substitution,
+            Action::Foul(_) => ActionKind::Foul,
+            Action::FreeThrow(_) => ActionKind::FreeThrow,
+            Action::Violation(_) => ActionKind::Violation,
+            Action::Game(_) => ActionKind::Game,
+            Action::Unknown(_) => ActionKind::Unknown,
+        }
+    }
+
+    /// The tri-code of the team credited with this action, if any
+    pub fn team(&self) -> Option<&str> {
+        self.info().team.as_deref()
+    }
+
+    pub fn home_score(&self) -> u16 {
+        self.info().home_score
+    }
+
+    pub fn away_score(&self) -> u16 {
+        self.info().away_score
+    }
+
+    /// The game time elapsed when this action happened
+    pub fn elapsed(&self) -> GameClock {
+        crate::clock::game_elapsed(self.quarter(), self.clock())
+    }
+
+    /// The id of the player credited with this action, if any
+    pub fn person_id(&self) -> Option<u64> {
+        self.info().person_id
+    }
+
+    /// The abbreviated name (e.g. `J. Tatum`) of the player
+    /// credited with this action, if any
+    pub fn player(&self) -> Option<&str> {
+        self.info().player.as_deref()
+    }
+
+    /// The id of the team with the ball after this action
+    pub fn possession(&self) -> Option<u64> {
+        self.info().possession
+    }
+
+    pub fn period_event(&self) -> Option<&PeriodEvent> {
+        match self.detail() {
+            ActionDetail::Period(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    pub fn substitution_kind(&self) -> Option<&SubstitutionKind> {
+        match self.detail() {
+            ActionDetail::Substitution(kind) => Some(kind),
+            _ => None,
+        }
+    }
+
+    /// If this action put points on the board
+    pub fn is_scoring(&self) -> bool {
+        matches!(self, Action::Points(_) | Action::FreeThrow(_))
+            && self.shot_result().map(ShotResult::is_made).unwrap_or(false)
+    }
+
+    pub fn info(&self) -> &ActionInfo {
         match self {
-            Action::Period(info) => &info.clock,
-            Action::JumpBall(info) => &info.clock,
-            Action::Points(info) => &info.clock,
-            Action::Rebound(info) => &info.clock,
-            Action::Stoppage(info) => &info.clock,
-            Action::Block(info) => &info.clock,
-            Action::Turnover(info) => &info.clock,
-            Action::Steal(info) => &info.clock,
-            Action::Timeout(info) => &info.clock,
-            Action::Substitution(info) => &info.clock,
-            Action::Foul(info) => &info.clock,
-            Action::FreeThrow(info) => &info.clock,
-            Action::Violation(info) => &info.clock,
-            Action::Game(info) => &info.clock,
-            Action::Unknown(info) => &info.clock,
+            Action::Period(info) => info,
+            Action::JumpBall(info) => info,
+            Action::Points(info) => info,
+            Action::Rebound(info) => info,
+            Action::Stoppage(info) => info,
+            Action::Block(info) => info,
+            Action::Turnover(info) => info,
+            Action::Steal(info) => info,
+            Action::Timeout(info) => info,
+            Action::Substitution(info) => info,
+            Action::Foul(info) => info,
+            Action::FreeThrow(info) => info,
+            Action::Violation(info) => info,
+            Action::Game(info) => info,
+            Action::Unknown(info) => info,
+        }
+    }
+
+    pub fn detail(&self) -> &ActionDetail {
+        &self.info().detail
+    }
+
+    /// The made/missed result for field goals and free throws
+    pub fn shot_result(&self) -> Option<&ShotResult> {
+        match self.detail() {
+            ActionDetail::Shot { result, .. } | ActionDetail::FreeThrow { result, .. } => {
+                Some(result)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn shot_kind(&self) -> Option<&ShotKind> {
+        match self.detail() {
+            ActionDetail::Shot { kind, .. } => Some(kind),
+            _ => None,
+        }
+    }
+
+    /// The points a field goal attempt was worth
+    pub fn shot_value(&self) -> Option<u8> {
+        match self.detail() {
+            ActionDetail::Shot { value, .. } => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn rebound_kind(&self) -> Option<&ReboundKind> {
+        match self.detail() {
+            ActionDetail::Rebound(kind) => Some(kind),
+            _ => None,
+        }
+    }
+
+    pub fn foul_kind(&self) -> Option<&FoulKind> {
+        match self.detail() {
+            ActionDetail::Foul { kind, .. } => Some(kind),
+            _ => None,
+        }
+    }
+
+    /// The fouler's personal foul count after this foul, as
+    /// reported by the play-by-play
+    pub fn foul_personal_total(&self) -> Option<u8> {
+        match self.detail() {
+            ActionDetail::Foul { personal_total, .. } => *personal_total,
+            _ => None,
+        }
+    }
+
+    pub fn turnover_kind(&self) -> Option<&TurnoverKind> {
+        match self.detail() {
+            ActionDetail::Turnover(kind) => Some(kind),
+            _ => None,
+        }
+    }
+
+    pub fn free_throw_sequence(&self) -> Option<&FreeThrowSequence> {
+        match self.detail() {
+            ActionDetail::FreeThrow { sequence, .. } => Some(sequence),
+            _ => None,
         }
     }
 }
@@ -106,11 +268,21 @@ impl Action {
 pub struct ActionInfo {
     #[serde(alias = "actionNumber")]
     number: i64,
-    clock: String,
+    clock: GameClock,
     desc: String,
     home_score: u16,
     away_score: u16,
     quarter: u8,
+    #[serde(default)]
+    team: Option<String>,
+    #[serde(default)]
+    person_id: Option<u64>,
+    #[serde(default)]
+    player: Option<String>,
+    #[serde(default)]
+    possession: Option<u64>,
+    #[serde(default)]
+    detail: ActionDetail,
 }
 
 impl Action {
@@ -122,21 +294,46 @@ impl Action {
         let number = value.get("actionNumber")?.as_i64()?;
         let ty = value.get("actionType")?.as_str()?;
         let quarter = value.get("period")?.as_u64()?;
-        let clock = duration_to_clock(value.get("clock")?.as_str()?)?;
+        let clock: GameClock = value
+            .get("clock")?
+            .as_str()?
+            .parse()
+            .map_err(|e| log::warn!("Failed to parse action clock: {}", e))
+            .ok()?;
         let home_score: u16 = value.get("scoreHome")?.as_str()?.parse().ok()?;
         let away_score: u16 = value.get("scoreAway")?.as_str()?.parse().ok()?;
         let mut inner = ActionInfo {
             number,
             quarter: quarter as u8,
-            clock: clock.to_string(),
+            clock,
             home_score,
             away_score,
             desc: String::new(),
+            team: value
+                .get("teamTricode")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+            person_id: value
+                .get("personId")
+                .and_then(|v| v.as_u64())
+                .filter(|id| *id != 0),
+            player: value
+                .get("playerNameI")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+            possession: value
+                .get("possession")
+                .and_then(|v| v.as_u64())
+                .filter(|id| *id != 0),
+            detail: ActionDetail::None,
         };
         let ret = match ty {
             "period" => {
                 let sub = value.get("subType")?.as_str()?.to_string();
                 inner.desc = format!("Q{} {}", inner.quarter, sub);
+                inner.detail = ActionDetail::Period(PeriodEvent::from(sub.as_str()));
                 Self::Period(inner)
             }
             "jumpball" => {
@@ -149,23 +346,35 @@ impl Action {
             "2pt" => {
                 let team = value.get("teamTricode")?.as_str()?;
                 let player = value.get("playerNameI")?.as_str()?;
-                let sub = value.get("subType")?.as_str()?;
-                let what = value.get("shotResult")?.as_str()?;
-                inner.desc = format!("{} 2pts {} {} {}", what, team, player, sub.to_lowercase());
+                let kind = ShotKind::from(value.get("subType")?.as_str()?);
+                let result = shot_result(&value);
+                inner.desc = format!("{}2pts {} {} {}", result.prefix(), team, player, kind);
+                inner.detail = ActionDetail::Shot {
+                    result,
+                    kind,
+                    value: 2,
+                };
                 Self::Points(inner)
             }
             "3pt" => {
                 let team = value.get("teamTricode")?.as_str()?;
                 let player = value.get("playerNameI")?.as_str()?;
-                let sub = value.get("subType")?.as_str()?;
-                inner.desc = format!("3pts {} {} {}", team, player, sub);
+                let kind = ShotKind::from(value.get("subType")?.as_str()?);
+                let result = shot_result(&value);
+                inner.desc = format!("{}3pts {} {} {}", result.prefix(), team, player, kind);
+                inner.detail = ActionDetail::Shot {
+                    result,
+                    kind,
+                    value: 3,
+                };
                 Self::Points(inner)
             }
             "rebound" => {
                 let team = value.get("teamTricode")?.as_str()?;
                 let player = value.get("playerNameI")?.as_str()?;
-                let sub = value.get("subType")?.as_str()?;
-                inner.desc = format!("{} rebound {} ({})", sub, player, team);
+                let kind = ReboundKind::from(value.get("subType")?.as_str()?);
+                inner.desc = format!("{} rebound {} ({})", kind, player, team);
+                inner.detail = ActionDetail::Rebound(kind);
                 Self::Rebound(inner)
             }
             "block" => {
@@ -180,7 +389,13 @@ impl Action {
                 } else {
                     home_team
                 };
+                let kind = value
+                    .get("subType")
+                    .and_then(|v| v.as_str())
+                    .map(TurnoverKind::from)
+                    .unwrap_or_else(|| TurnoverKind::Other(String::new()));
                 inner.desc = format!("Turnover {} -> {}", from, to);
+                inner.detail = ActionDetail::Turnover(kind);
                 Self::Turnover(inner)
             }
             "steal" => {
@@ -200,22 +415,38 @@ impl Action {
             }
             "substitution" => {
                 let who = value.get("playerNameI")?.as_str()?;
-                let direction = value.get("subType")?.as_str()?;
-                inner.desc = format!("Sub {} {}", who, direction);
+                let kind = SubstitutionKind::from(value.get("subType")?.as_str()?);
+                inner.desc = format!("Sub {} {}", who, kind);
+                inner.detail = ActionDetail::Substitution(kind);
                 Self::Substitution(inner)
             }
             "foul" => {
                 let to = value.get("playerNameI")?.as_str()?;
-                let from = value.get("foulDrawnPlayerName")?.as_str()?;
-                let from_total = value.get("foulPersonalTotal")?.as_str()?;
-                inner.desc = format!("Foul {} <- {} ({})", to, from, from_total);
+                let from = value.get("foulDrawnPlayerName").and_then(|v| v.as_str());
+                let personal_total = value.get("foulPersonalTotal").and_then(lenient_u8);
+                let kind = value
+                    .get("subType")
+                    .and_then(|v| v.as_str())
+                    .map(FoulKind::from)
+                    .unwrap_or_else(|| FoulKind::Other(String::new()));
+                let total = personal_total.map(|t| t.to_string()).unwrap_or_default();
+                inner.desc = if let Some(from) = from {
+                    format!("Foul {} <- {} ({})", to, from, total)
+                } else {
+                    format!("Foul {} {} ({})", to, kind, total)
+                };
+                inner.detail = ActionDetail::Foul {
+                    kind,
+                    personal_total,
+                };
                 Self::Foul(inner)
             }
             "freethrow" => {
                 let who = value.get("playerNameI")?.as_str()?;
-                let which = value.get("subType")?.as_str()?;
-                let result = value.get("shotResult")?.as_str()?;
-                inner.desc = format!("Free Throw {} {} {}", who, which, result);
+                let sequence = FreeThrowSequence::from(value.get("subType")?.as_str()?);
+                let result = ShotResult::from(value.get("shotResult")?.as_str()?);
+                inner.desc = format!("Free Throw {} {} {}", who, sequence, result);
+                inner.detail = ActionDetail::FreeThrow { sequence, result };
                 Self::FreeThrow(inner)
             }
             "violation" => {
@@ -230,26 +461,497 @@ impl Action {
     }
 }
 
-pub fn duration_to_clock(d: &str) -> Option<String> {
-    let mut chars = d.chars();
-    let p = chars.next()?;
-    if p != 'P' {
-        return None;
+/// Older play-by-play feeds leave `shotResult` off some field
+/// goals, those are kept with an unknown result
+fn shot_result(value: &serde_json::Map<String, serde_json::Value>) -> ShotResult {
+    value
+        .get("shotResult")
+        .and_then(|v| v.as_str())
+        .map(ShotResult::from)
+        .unwrap_or(ShotResult::Unknown)
+}
+
+/// The play-by-play is inconsistent about sending counts
+/// as numbers or strings
+fn lenient_u8(value: &serde_json::Value) -> Option<u8> {
+    use std::convert::TryFrom;
+    match value {
+        serde_json::Value::Number(n) => n.as_u64().and_then(|n| u8::try_from(n).ok()),
+        serde_json::Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// The variant of an [`Action`] without its info, used to
+/// select which kinds of plays are worth displaying
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum ActionKind {
+    Period,
+    JumpBall,
+    Points,
+    Rebound,
+    Stoppage,
+    Block,
+    Turnover,
+    Steal,
+    Timeout,
+    Substitution,
+    Foul,
+    FreeThrow,
+    Violation,
+    Game,
+    Unknown,
+}
+
+impl ActionKind {
+    pub const ALL: [ActionKind; 15] = [
+        ActionKind::Period,
+        ActionKind::JumpBall,
+        ActionKind::Points,
+        ActionKind::Rebound,
+        ActionKind::Stoppage,
+        ActionKind::Block,
+        ActionKind::Turnover,
+        ActionKind::Steal,
+        ActionKind::Timeout,
+        ActionKind::Substitution,
+        ActionKind::Foul,
+        ActionKind::FreeThrow,
+        ActionKind::Violation,
+        ActionKind::Game,
+        ActionKind::Unknown,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ActionKind::Period => "period",
+            ActionKind::JumpBall => "jumpBall",
+            ActionKind::Points => "points",
+            ActionKind::Rebound => "rebound",
+            ActionKind::Stoppage => "stoppage",
+            ActionKind::Block => "block",
+            ActionKind::Turnover => "turnover",
+            ActionKind::Steal => "steal",
+            ActionKind::Timeout => "timeout",
+            ActionKind::Substitution => "substitution",
+            ActionKind::Foul => "foul",
+            ActionKind::FreeThrow => "freeThrow",
+            ActionKind::Violation => "violation",
+            ActionKind::Game => "game",
+            ActionKind::Unknown => "unknown",
+        }
+    }
+}
+
+impl std::str::FromStr for ActionKind {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(ActionKind::name).collect();
+                format!(
+                    "unknown action kind {:?}, expected one of {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// The typed `subType`/`shotResult` information for an action,
+/// only the action types that carry a meaningful sub-type have
+/// a variant other than `None`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ActionDetail {
+    #[default]
+    None,
+    Shot {
+        result: ShotResult,
+        kind: ShotKind,
+        /// 2 or 3 points
+        value: u8,
+    },
+    Rebound(ReboundKind),
+    Foul {
+        kind: FoulKind,
+        /// The fouler's personal foul count after this foul
+        personal_total: Option<u8>,
+    },
+    Turnover(TurnoverKind),
+    FreeThrow {
+        sequence: FreeThrowSequence,
+        result: ShotResult,
+    },
+    Substitution(SubstitutionKind),
+    Period(PeriodEvent),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ShotResult {
+    Made,
+    Missed,
+    /// The action didn't say
+    Unknown,
+    Other(String),
+}
+
+impl ShotResult {
+    pub fn is_made(&self) -> bool {
+        matches!(self, Self::Made)
+    }
+
+    /// The result followed by a space, empty when it is unknown
+    fn prefix(&self) -> String {
+        match self {
+            Self::Unknown => String::new(),
+            _ => format!("{} ", self),
+        }
+    }
+}
+
+impl From<&str> for ShotResult {
+    fn from(s: &str) -> Self {
+        match s.to_ascii_lowercase().as_str() {
+            "made" => Self::Made,
+            "missed" => Self::Missed,
+            _ => Self::Other(s.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for ShotResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Made => "Made".fmt(f),
+            Self::Missed => "Missed".fmt(f),
+            Self::Unknown => "Unknown".fmt(f),
+            Self::Other(s) => s.fmt(f),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ShotKind {
+    JumpShot,
+    Layup,
+    Dunk,
+    Hook,
+    Other(String),
+}
+
+impl From<&str> for ShotKind {
+    fn from(s: &str) -> Self {
+        match s.to_ascii_lowercase().as_str() {
+            "jump shot" => Self::JumpShot,
+            "layup" => Self::Layup,
+            "dunk" => Self::Dunk,
+            "hook" => Self::Hook,
+            _ => Self::Other(s.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for ShotKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::JumpShot => "jump shot".fmt(f),
+            Self::Layup => "layup".fmt(f),
+            Self::Dunk => "dunk".fmt(f),
+            Self::Hook => "hook".fmt(f),
+            Self::Other(s) => s.to_lowercase().fmt(f),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ReboundKind {
+    Offensive,
+    Defensive,
+    Other(String),
+}
+
+impl From<&str> for ReboundKind {
+    fn from(s: &str) -> Self {
+        match s.to_ascii_lowercase().as_str() {
+            "offensive" => Self::Offensive,
+            "defensive" => Self::Defensive,
+            _ => Self::Other(s.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for ReboundKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Offensive => "offensive".fmt(f),
+            Self::Defensive => "defensive".fmt(f),
+            Self::Other(s) => s.fmt(f),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FoulKind {
+    Personal,
+    Offensive,
+    Technical,
+    Flagrant,
+    Other(String),
+}
+
+impl From<&str> for FoulKind {
+    fn from(s: &str) -> Self {
+        let lower = s.to_ascii_lowercase();
+        match lower.as_str() {
+            "personal" => Self::Personal,
+            "offensive" => Self::Offensive,
+            "technical" => Self::Technical,
+            _ if lower.starts_with("flagrant") => Self::Flagrant,
+            _ => Self::Other(s.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for FoulKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Personal => "personal".fmt(f),
+            Self::Offensive => "offensive".fmt(f),
+            Self::Technical => "technical".fmt(f),
+            Self::Flagrant => "flagrant".fmt(f),
+            Self::Other(s) => s.fmt(f),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TurnoverKind {
+    BadPass,
+    LostBall,
+    OutOfBounds,
+    Traveling,
+    OffensiveFoul,
+    ShotClock,
+    Other(String),
+}
+
+impl From<&str> for TurnoverKind {
+    fn from(s: &str) -> Self {
+        let lower = s.to_ascii_lowercase();
+        match lower.as_str() {
+            "bad pass" => Self::BadPass,
+            "lost ball" => Self::LostBall,
+            "traveling" => Self::Traveling,
+            "offensive foul" => Self::OffensiveFoul,
+            "shot clock" => Self::ShotClock,
+            _ if lower.starts_with("out-of-bounds") => Self::OutOfBounds,
+            _ => Self::Other(s.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for TurnoverKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BadPass => "bad pass".fmt(f),
+            Self::LostBall => "lost ball".fmt(f),
+            Self::OutOfBounds => "out of bounds".fmt(f),
+            Self::Traveling => "traveling".fmt(f),
+            Self::OffensiveFoul => "offensive foul".fmt(f),
+            Self::ShotClock => "shot clock".fmt(f),
+            Self::Other(s) => s.fmt(f),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SubstitutionKind {
+    In,
+    Out,
+    Other(String),
+}
+
+impl From<&str> for SubstitutionKind {
+    fn from(s: &str) -> Self {
+        match s.to_ascii_lowercase().as_str() {
+            "in" => Self::In,
+            "out" => Self::Out,
+            _ => Self::Other(s.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for SubstitutionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::In => "in".fmt(f),
+            Self::Out => "out".fmt(f),
+            Self::Other(s) => s.fmt(f),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PeriodEvent {
+    Start,
+    End,
+    Other(String),
+}
+
+impl From<&str> for PeriodEvent {
+    fn from(s: &str) -> Self {
+        match s.to_ascii_lowercase().as_str() {
+            "start" => Self::Start,
+            "end" => Self::End,
+            _ => Self::Other(s.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for PeriodEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Start => "start".fmt(f),
+            Self::End => "end".fmt(f),
+            Self::Other(s) => s.fmt(f),
+        }
+    }
+}
+
+/// Which free throw of a trip to the line this was, parsed
+/// from sub-types like `1 of 2`, `flagrant 2 of 2` or `technical`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FreeThrowSequence {
+    Attempt { number: u8, of: u8 },
+    Flagrant { number: u8, of: u8 },
+    Technical,
+    Other(String),
+}
+
+impl FreeThrowSequence {
+    /// If this was the last free throw of the trip to the line
+    pub fn is_last(&self) -> bool {
+        match self {
+            Self::Attempt { number, of } | Self::Flagrant { number, of } => number >= of,
+            Self::Technical => true,
+            Self::Other(_) => false,
+        }
     }
-    let t = chars.next()?;
-    if t != 'T' {
+}
+
+fn parse_n_of_m(s: &str) -> Option<(u8, u8)> {
+    let mut parts = s.trim().split(" of ");
+    let number = parts.next()?.trim().parse().ok()?;
+    let of = parts.next()?.trim().parse().ok()?;
+    if parts.next().is_some() {
         return None;
     }
-    let mut ret = String::new();
-    for ch in chars {
-        if ch == 'M' {
-            ret.push(':');
-            continue;
+    Some((number, of))
+}
+
+impl From<&str> for FreeThrowSequence {
+    fn from(s: &str) -> Self {
+        let lower = s.to_ascii_lowercase();
+        if lower.starts_with("technical") {
+            return Self::Technical;
+        }
+        if let Some(rest) = lower.strip_prefix("flagrant") {
+            if let Some((number, of)) = parse_n_of_m(rest) {
+                return Self::Flagrant { number, of };
+            }
         }
-        if ch == '.' {
-            break;
+        if let Some((number, of)) = parse_n_of_m(&lower) {
+            return Self::Attempt { number, of };
+        }
+        Self::Other(s.to_string())
+    }
+}
+
+impl std::fmt::Display for FreeThrowSequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Attempt { number, of } => write!(f, "{} of {}", number, of),
+            Self::Flagrant { number, of } => write!(f, "flagrant {} of {}", number, of),
+            Self::Technical => "technical".fmt(f),
+            Self::Other(s) => s.fmt(f),
         }
-        ret.push(ch)
     }
-    Some(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::action;
+    use serde_json::json;
+
+    #[test]
+    fn free_throw_sequence() {
+        assert_eq!(
+            FreeThrowSequence::from("1 of 2"),
+            FreeThrowSequence::Attempt { number: 1, of: 2 }
+        );
+        assert_eq!(
+            FreeThrowSequence::from("Flagrant 2 of 2"),
+            FreeThrowSequence::Flagrant { number: 2, of: 2 }
+        );
+        assert_eq!(
+            FreeThrowSequence::from("Technical"),
+            FreeThrowSequence::Technical
+        );
+        assert_eq!(
+            FreeThrowSequence::from("1 of 2 of 3"),
+            FreeThrowSequence::Other("1 of 2 of 3".to_string())
+        );
+        assert!(!FreeThrowSequence::from("1 of 2").is_last());
+        assert!(FreeThrowSequence::from("2 of 2").is_last());
+        assert!(FreeThrowSequence::from("technical").is_last());
+    }
+
+    #[test]
+    fn sub_types() {
+        assert_eq!(ShotKind::from("Jump Shot"), ShotKind::JumpShot);
+        assert_eq!(FoulKind::from("flagrant-type-1"), FoulKind::Flagrant);
+        assert_eq!(
+            TurnoverKind::from("out-of-bounds-lost-ball"),
+            TurnoverKind::OutOfBounds
+        );
+        assert_eq!(
+            ReboundKind::from("team"),
+            ReboundKind::Other("team".to_string())
+        );
+    }
+
+    #[test]
+    fn shot_with_result() {
+        let action = action(json!({
+            "actionType": "3pt",
+            "subType": "Jump Shot",
+            "shotResult": "Made",
+        }));
+        assert_eq!(action.shot_result(), Some(&ShotResult::Made));
+        assert_eq!(action.shot_value(), Some(3));
+        assert!(action.is_scoring());
+        assert_eq!(action.desc(), "Made 3pts BOS J. Tatum jump shot");
+    }
+
+    #[test]
+    fn shot_without_result() {
+        let action = action(json!({"actionType": "3pt", "subType": "Jump Shot"}));
+        assert_eq!(action.shot_result(), Some(&ShotResult::Unknown));
+        assert!(!action.is_scoring());
+        assert_eq!(action.desc(), "3pts BOS J. Tatum jump shot");
+    }
 }
@@ -0,0 +1,376 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    action::{Action, ActionKind, ShotResult},
+    clock::GameClock,
+};
+
+/// Thresholds used when looking for runs and droughts
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MomentumConfig {
+    /// The fewest points a team needs to score for a run to be reported
+    pub min_run_points: u16,
+    /// The most points the other team can score during a run
+    pub max_run_allowed: u16,
+    /// How long a team has to go without a field goal for it to
+    /// be reported as a drought
+    pub min_drought: GameClock,
+}
+
+impl Default for MomentumConfig {
+    fn default() -> Self {
+        Self {
+            min_run_points: 8,
+            max_run_allowed: 4,
+            min_drought: GameClock::from_secs(3 * 60),
+        }
+    }
+}
+
+/// The momentum of a game built from the score progression
+/// of its play-by-play
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Momentum {
+    /// The run currently in progress, if any
+    pub run: Option<ScoringRun>,
+    pub home_drought: Option<Drought>,
+    pub away_drought: Option<Drought>,
+    pub lead_changes: u16,
+    pub ties: u16,
+    pub home_largest_lead: u16,
+    pub away_largest_lead: u16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoringRun {
+    pub team: String,
+    pub points: u16,
+    pub allowed: u16,
+}
+
+impl std::fmt::Display for ScoringRun {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}-{} run", self.team, self.points, self.allowed)
+    }
+}
+
+/// The time a team has gone without a made field goal
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Drought {
+    pub team: String,
+    pub length: GameClock,
+}
+
+impl std::fmt::Display for Drought {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} no FG {}", self.team, self.length)
+    }
+}
+
+/// Made and attempted shots for one team
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ShootingSplits {
+    pub field_goals_made: u16,
+    pub field_goals_attempted: u16,
+    pub threes_made: u16,
+    pub threes_attempted: u16,
+    pub free_throws_made: u16,
+    pub free_throws_attempted: u16,
+}
+
+impl ShootingSplits {
+    /// The shots `team` took in `period`, or the whole game
+    /// when `period` is `None`
+    pub fn from_plays(plays: &[Action], team: &str, period: Option<u8>) -> Self {
+        let mut ret = Self::default();
+        for play in plays.iter().filter(|p| p.team() == Some(team)) {
+            if period.map(|q| play.quarter() != q).unwrap_or(false) {
+                continue;
+            }
+            let made = play.shot_result().map(|r| r.is_made()).unwrap_or(false);
+            match (play.kind(), play.shot_value()) {
+                (ActionKind::Points, Some(value)) => {
+                    ret.field_goals_attempted += 1;
+                    ret.field_goals_made += made as u16;
+                    if value == 3 {
+                        ret.threes_attempted += 1;
+                        ret.threes_made += made as u16;
+                    }
+                }
+                (ActionKind::FreeThrow, _) => {
+                    ret.free_throws_attempted += 1;
+                    ret.free_throws_made += made as u16;
+                }
+                _ => {}
+            }
+        }
+        ret
+    }
+}
+
+/// A change in the score and who it went to
+struct Score {
+    home: bool,
+    points: u16,
+}
+
+impl Momentum {
+    /// Analyse `plays`, which are expected to be sorted by action number
+    pub fn from_plays(
+        plays: &[Action],
+        home_team: &str,
+        away_team: &str,
+        config: &MomentumConfig,
+    ) -> Self {
+        let mut ret = Self::default();
+        let mut scores = Vec::new();
+        let (mut home, mut away) = (0u16, 0u16);
+        let mut leader = 0i32;
+        for play in plays {
+            let (new_home, new_away) = (play.home_score(), play.away_score());
+            if new_home == home && new_away == away {
+                continue;
+            }
+            if new_home > home {
+                scores.push(Score {
+                    home: true,
+                    points: new_home - home,
+                });
+            }
+            if new_away > away {
+                scores.push(Score {
+                    home: false,
+                    points: new_away - away,
+                });
+            }
+            home = new_home;
+            away = new_away;
+            let margin = i32::from(home) - i32::from(away);
+            if margin == 0 {
+                ret.ties += 1;
+            } else if margin.signum() != leader {
+                if leader != 0 {
+                    ret.lead_changes += 1;
+                }
+                leader = margin.signum();
+            }
+            if margin > 0 {
+                ret.home_largest_lead = ret.home_largest_lead.max(margin as u16);
+            } else {
+                ret.away_largest_lead = ret.away_largest_lead.max((-margin) as u16);
+            }
+        }
+        ret.run = current_run(&scores, home_team, away_team, config);
+        if let Some(now) = plays.last().map(Action::elapsed) {
+            ret.home_drought = drought(plays, home_team, true, now, config);
+            ret.away_drought = drought(plays, away_team, false, now, config);
+        }
+        ret
+    }
+
+    /// The longest of the two droughts, if any
+    pub fn longest_drought(&self) -> Option<&Drought> {
+        match (&self.home_drought, &self.away_drought) {
+            (Some(home), Some(away)) if away.length > home.length => Some(away),
+            (Some(home), _) => Some(home),
+            (None, away) => away.as_ref(),
+        }
+    }
+}
+
+/// Walk back from the most recent score to find the stretch that
+/// best favours one team while the other stays under the allowed
+/// points
+fn current_run(
+    scores: &[Score],
+    home_team: &str,
+    away_team: &str,
+    config: &MomentumConfig,
+) -> Option<ScoringRun> {
+    let mut best: Option<ScoringRun> = None;
+    for &running_home in &[true, false] {
+        let (mut points, mut allowed) = (0u16, 0u16);
+        let mut best_for_team = (0u16, 0u16);
+        for score in scores.iter().rev() {
+            if score.home == running_home {
+                points += score.points;
+            } else {
+                allowed += score.points;
+                if allowed > config.max_run_allowed {
+                    break;
+                }
+            }
+            if i32::from(points) - i32::from(allowed)
+                > i32::from(best_for_team.0) - i32::from(best_for_team.1)
+            {
+                best_for_team = (points, allowed);
+            }
+        }
+        let (points, allowed) = best_for_team;
+        if points < config.min_run_points {
+            continue;
+        }
+        let is_better = best
+            .as_ref()
+            .map(|b| points - allowed > b.points - b.allowed)
+            .unwrap_or(true);
+        if is_better {
+            best = Some(ScoringRun {
+                team: if running_home { home_team } else { away_team }.to_string(),
+                points,
+                allowed,
+            });
+        }
+    }
+    best
+}
+
+/// If `play` put points on the board, older feed entries don't have
+/// a shot result so for those the team's score going up since the
+/// `previous` play is taken instead
+fn scored(play: &Action, previous: Option<&Action>, home: bool) -> bool {
+    if play.shot_result() != Some(&ShotResult::Unknown) {
+        return play.is_scoring();
+    }
+    let score = |p: &Action| if home { p.home_score() } else { p.away_score() };
+    score(play) > previous.map(score).unwrap_or(0)
+}
+
+fn drought(
+    plays: &[Action],
+    team: &str,
+    home: bool,
+    now: GameClock,
+    config: &MomentumConfig,
+) -> Option<Drought> {
+    let since = (0..plays.len())
+        .rev()
+        .find(|&i| {
+            let play = &plays[i];
+            let previous = i.checked_sub(1).map(|p| &plays[p]);
+            play.kind() == ActionKind::Points
+                && play.team() == Some(team)
+                && scored(play, previous, home)
+        })
+        .map(|i| plays[i].elapsed())
+        .unwrap_or_default();
+    let length = now - since;
+    if length < config.min_drought {
+        return None;
+    }
+    Some(Drought {
+        team: team.to_string(),
+        length,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{action, made_shot, AWAY, HOME};
+    use serde_json::json;
+
+    /// NYK scores first, then BOS goes on a 10-0 run
+    fn plays() -> Vec<Action> {
+        vec![
+            made_shot(1, "PT11M40.00S", AWAY, 0, 2),
+            made_shot(2, "PT11M00.00S", HOME, 2, 2),
+            made_shot(3, "PT10M00.00S", HOME, 4, 2),
+            made_shot(4, "PT09M00.00S", HOME, 6, 2),
+            made_shot(5, "PT08M00.00S", HOME, 8, 2),
+            made_shot(6, "PT07M00.00S", HOME, 10, 2),
+        ]
+    }
+
+    #[test]
+    fn run_and_drought() {
+        let momentum = Momentum::from_plays(&plays(), HOME, AWAY, &MomentumConfig::default());
+        assert_eq!(
+            momentum.run,
+            Some(ScoringRun {
+                team: HOME.to_string(),
+                points: 10,
+                allowed: 0,
+            })
+        );
+        assert_eq!(momentum.home_drought, None);
+        assert_eq!(
+            momentum.longest_drought(),
+            Some(&Drought {
+                team: AWAY.to_string(),
+                length: GameClock::from_secs(4 * 60 + 40),
+            })
+        );
+    }
+
+    #[test]
+    fn drought_without_shot_result() {
+        let shot = |number: i64, clock: &str, team: &str, home: u16, away: u16| {
+            action(json!({
+                "actionNumber": number,
+                "actionType": "2pt",
+                "subType": "Layup",
+                "clock": clock,
+                "teamTricode": team,
+                "scoreHome": home.to_string(),
+                "scoreAway": away.to_string(),
+            }))
+        };
+        let plays = vec![
+            shot(1, "PT11M40.00S", AWAY, 0, 2),
+            shot(2, "PT11M00.00S", HOME, 2, 2),
+            shot(3, "PT10M00.00S", AWAY, 2, 2),
+            shot(4, "PT07M00.00S", HOME, 4, 2),
+        ];
+        let momentum = Momentum::from_plays(&plays, HOME, AWAY, &MomentumConfig::default());
+        assert_eq!(momentum.home_drought, None);
+        assert_eq!(
+            momentum.away_drought,
+            Some(Drought {
+                team: AWAY.to_string(),
+                length: GameClock::from_secs(4 * 60 + 40),
+            })
+        );
+    }
+
+    #[test]
+    fn leads_and_ties() {
+        let momentum = Momentum::from_plays(&plays(), HOME, AWAY, &MomentumConfig::default());
+        assert_eq!(momentum.ties, 1);
+        assert_eq!(momentum.lead_changes, 1);
+        assert_eq!(momentum.home_largest_lead, 8);
+        assert_eq!(momentum.away_largest_lead, 2);
+    }
+
+    #[test]
+    fn no_run_when_trading_baskets() {
+        let plays = vec![
+            made_shot(1, "PT11M40.00S", HOME, 2, 0),
+            made_shot(2, "PT11M20.00S", AWAY, 2, 2),
+            made_shot(3, "PT11M00.00S", HOME, 4, 2),
+            made_shot(4, "PT10M40.00S", AWAY, 4, 4),
+        ];
+        let momentum = Momentum::from_plays(&plays, HOME, AWAY, &MomentumConfig::default());
+        assert_eq!(momentum.run, None);
+        assert_eq!(momentum.longest_drought(), None);
+        assert_eq!(momentum.lead_changes, 0);
+        assert_eq!(momentum.ties, 2);
+    }
+
+    #[test]
+    fn shooting_splits() {
+        let splits = ShootingSplits::from_plays(&plays(), HOME, Some(1));
+        assert_eq!(splits.field_goals_made, 5);
+        assert_eq!(splits.field_goals_attempted, 5);
+        assert_eq!(splits.threes_attempted, 0);
+        assert_eq!(
+            ShootingSplits::from_plays(&plays(), HOME, Some(2)),
+            ShootingSplits::default()
+        );
+    }
+}
@@ -0,0 +1,555 @@
+use std::{convert::Infallible, fmt, sync::OnceLock};
+
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+use fontdue::{Font, FontSettings};
+
+use crate::{Line, CELL_SEPARATOR};
+
+/// The e-ink panel is mounted on its side, these are the
+/// dimensions after rotating
+pub const WIDTH: u32 = 250;
+pub const HEIGHT: u32 = 122;
+/// The space above the first line
+pub const TOP: i32 = 5;
+/// The extra space a blank line adds on top of its height
+pub const BLANK_SPACING: i32 = 5;
+/// The space kept clear at the sides by rows of cells
+pub const MARGIN: i32 = 5;
+/// The space between the columns of a table
+pub const TABLE_GAP: i32 = 6;
+pub const SMALL_PX: f32 = 12.0;
+pub const MEDIUM_PX: f32 = 16.0;
+pub const LARGE_PX: f32 = 24.0;
+/// Coverage at or above this is drawn, the panel has no grays
+const THRESHOLD: u8 = 128;
+
+/// The font the Python renderer defaults to, bundled so the
+/// Pi doesn't need it installed
+pub const BUNDLED_FONT: &[u8] = include_bytes!("../fonts/DejaVuSans-Bold.ttf");
+
+/// A 1-bit image packed 8 pixels to a byte, most significant bit
+/// first, each row starting on a new byte. A set bit is a black
+/// pixel. As a [`DrawTarget`] this stands in for the panel when
+/// testing, [`BinaryColor::On`] is black
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bitmap {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl Bitmap {
+    /// An all white image
+    pub fn new(width: u32, height: u32) -> Self {
+        let stride = Self::stride_for(width);
+        Self {
+            width,
+            height,
+            data: vec![0; stride * height as usize],
+        }
+    }
+
+    fn stride_for(width: u32) -> usize {
+        (width as usize).div_ceil(8)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The bytes in one row
+    pub fn stride(&self) -> usize {
+        Self::stride_for(self.width)
+    }
+
+    /// The packed pixels, see [`Bitmap`] for the layout
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+        let (idx, bit) = self.index(x, y);
+        self.data[idx] & bit != 0
+    }
+
+    /// Set the pixel at `x`, `y` to black, pixels outside the
+    /// image are ignored
+    pub fn set(&mut self, x: i32, y: i32, black: bool) {
+        if x < 0 || y < 0 || x as u32 >= self.width || y as u32 >= self.height {
+            return;
+        }
+        let (idx, bit) = self.index(x as u32, y as u32);
+        if black {
+            self.data[idx] |= bit;
+        } else {
+            self.data[idx] &= !bit;
+        }
+    }
+
+    fn index(&self, x: u32, y: u32) -> (usize, u8) {
+        let idx = y as usize * self.stride() + x as usize / 8;
+        (idx, 0x80 >> (x % 8))
+    }
+
+    /// Encode as a 1-bit grayscale PNG
+    pub fn to_png(&self) -> Result<Vec<u8>, png::EncodingError> {
+        let mut ret = Vec::new();
+        let mut encoder = png::Encoder::new(&mut ret, self.width, self.height);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::One);
+        let mut writer = encoder.write_header()?;
+        // PNG grayscale uses 0 for black
+        let inverted: Vec<u8> = self.data.iter().map(|b| !b).collect();
+        writer.write_image_data(&inverted)?;
+        writer.finish()?;
+        Ok(ret)
+    }
+}
+
+impl OriginDimensions for Bitmap {
+    fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+}
+
+impl DrawTarget for Bitmap {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            self.set(point.x, point.y, color.is_on());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum RenderError {
+    /// The font data couldn't be parsed
+    Font(&'static str),
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Font(e) => write!(f, "invalid font: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+/// A run of text and where its top left corner goes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placed<'a> {
+    pub text: &'a str,
+    pub px: f32,
+    pub left: i32,
+    pub top: i32,
+}
+
+/// Where each line of a screen is drawn
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout<'a> {
+    pub placed: Vec<Placed<'a>>,
+    /// The row just below the last line
+    pub bottom: i32,
+}
+
+/// Lays out [`Line`]s like `lib/render_eink.py` does, each line
+/// centered under the last starting [`TOP`] pixels down. The layout
+/// is approximate: PIL's `getsize` gives each line the height of its
+/// own text, so one without descenders sits a little higher there,
+/// while every line here is the font's full height, see
+/// [`Renderer::line_height`]. Text is drawn in [`BinaryColor::On`]
+/// over [`BinaryColor::Off`]
+pub struct Renderer {
+    font: Font,
+}
+
+impl Renderer {
+    pub fn bundled() -> Result<Self, RenderError> {
+        Self::from_font_bytes(BUNDLED_FONT)
+    }
+
+    /// The [`bundled`](Self::bundled) renderer, loaded the first time
+    /// it is needed and kept for the life of the program. `None` if
+    /// the font failed to load, which is logged once
+    pub fn shared() -> Option<&'static Self> {
+        static RENDERER: OnceLock<Option<Renderer>> = OnceLock::new();
+        RENDERER
+            .get_or_init(|| {
+                Self::bundled()
+                    .map_err(|e| log::error!("failed to load the bundled font: {}", e))
+                    .ok()
+            })
+            .as_ref()
+    }
+
+    pub fn from_font_bytes(bytes: &[u8]) -> Result<Self, RenderError> {
+        let font = Font::from_bytes(bytes, FontSettings::default()).map_err(RenderError::Font)?;
+        Ok(Self { font })
+    }
+
+    pub fn render(&self, lines: &[Line]) -> Bitmap {
+        let mut ret = Bitmap::new(WIDTH, HEIGHT);
+        match self.draw(lines, &mut ret) {
+            Ok(()) => ret,
+            Err(e) => match e {},
+        }
+    }
+
+    /// Clear `target` and draw `lines` on it, centered across
+    /// its width
+    pub fn draw<D>(&self, lines: &[Line], target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        target.clear(BinaryColor::Off)?;
+        let width = target.bounding_box().size.width as i32;
+        for placed in self.layout(lines, width).placed {
+            self.draw_text(target, placed.text, placed.px, placed.left, placed.top)?;
+        }
+        Ok(())
+    }
+
+    /// Where `lines` go on a screen `width` pixels wide. Lines are
+    /// centered and an empty line adds [`BLANK_SPACING`] on top of
+    /// its height. A row of cells, split by [`CELL_SEPARATOR`], is
+    /// spread across the screen: the first cell against the left
+    /// [`MARGIN`], the last against the right and any others
+    /// centered at even steps between. Two or more rows in a row with
+    /// the same number of cells are a table instead, see
+    /// [`Renderer::table`]
+    pub fn layout<'a>(&self, lines: &'a [Line], width: i32) -> Layout<'a> {
+        let mut placed = Vec::new();
+        let mut top = TOP;
+        let mut idx = 0;
+        while idx < lines.len() {
+            let line = &lines[idx];
+            let (text, px) = text_and_px(line);
+            let height = self.line_height(px);
+            idx += 1;
+            if text.is_empty() {
+                top += height + BLANK_SPACING;
+                continue;
+            }
+            if line.has_cells() {
+                let columns = text.split(CELL_SEPARATOR).count();
+                let rows = 1 + lines[idx..]
+                    .iter()
+                    .take_while(|l| {
+                        l.has_cells() && l.text().split(CELL_SEPARATOR).count() == columns
+                    })
+                    .count();
+                if rows > 1 {
+                    let rows = &lines[idx - 1..idx - 1 + rows];
+                    idx += rows.len() - 1;
+                    top = self.table(rows, width, top, &mut placed);
+                } else {
+                    placed.extend(self.spread(text, px, width, top));
+                    top += height;
+                }
+                continue;
+            }
+            placed.push(Placed {
+                text,
+                px,
+                left: width / 2 - self.text_width(text, px) / 2,
+                top,
+            });
+            top += height;
+        }
+        Layout {
+            placed,
+            bottom: top,
+        }
+    }
+
+    /// Lay out `rows` of cells as columns as wide as their widest
+    /// cell with [`TABLE_GAP`] between, the first column left aligned
+    /// and the rest right aligned, and the whole table centered.
+    /// Returns the row below the table
+    fn table<'a>(
+        &self,
+        rows: &'a [Line],
+        width: i32,
+        mut top: i32,
+        placed: &mut Vec<Placed<'a>>,
+    ) -> i32 {
+        let mut widths: Vec<i32> = Vec::new();
+        for row in rows {
+            let (text, px) = text_and_px(row);
+            for (col, cell) in text.split(CELL_SEPARATOR).enumerate() {
+                let cell_width = self.text_width(cell, px);
+                match widths.get_mut(col) {
+                    Some(w) => *w = (*w).max(cell_width),
+                    None => widths.push(cell_width),
+                }
+            }
+        }
+        let total = widths.iter().sum::<i32>() + TABLE_GAP * (widths.len() as i32 - 1);
+        let left = ((width - total) / 2).max(MARGIN);
+        for row in rows {
+            let (text, px) = text_and_px(row);
+            let mut column_left = left;
+            for (col, cell) in text.split(CELL_SEPARATOR).enumerate() {
+                if !cell.is_empty() {
+                    let cell_left = if col == 0 {
+                        column_left
+                    } else {
+                        column_left + widths[col] - self.text_width(cell, px)
+                    };
+                    placed.push(Placed {
+                        text: cell,
+                        px,
+                        left: cell_left,
+                        top,
+                    });
+                }
+                column_left += widths[col] + TABLE_GAP;
+            }
+            top += self.line_height(px);
+        }
+        top
+    }
+
+    fn spread<'a>(&self, text: &'a str, px: f32, width: i32, top: i32) -> Vec<Placed<'a>> {
+        let cells: Vec<_> = text.split(CELL_SEPARATOR).collect();
+        let last = cells.len() as i32 - 1;
+        cells
+            .into_iter()
+            .enumerate()
+            .filter(|(_, cell)| !cell.is_empty())
+            .map(|(i, cell)| {
+                let i = i as i32;
+                let cell_width = self.text_width(cell, px);
+                let left = if i == 0 {
+                    MARGIN
+                } else if i == last {
+                    width - MARGIN - cell_width
+                } else {
+                    width * i / last - cell_width / 2
+                };
+                Placed {
+                    text: cell,
+                    px,
+                    left,
+                    top,
+                }
+            })
+            .collect()
+    }
+
+    /// Render the contents of a data file, see [`Line::parse`]
+    pub fn render_str(&self, screen: &str) -> Bitmap {
+        let lines: Vec<_> = screen.lines().filter_map(Line::parse).collect();
+        self.render(&lines)
+    }
+
+    /// The height a line of text at `px` takes up, ascent to
+    /// descent whatever the text
+    pub fn line_height(&self, px: f32) -> i32 {
+        self.font
+            .horizontal_line_metrics(px)
+            .map(|m| (m.ascent - m.descent).ceil() as i32)
+            .unwrap_or(px as i32)
+    }
+
+    fn ascent(&self, px: f32) -> f32 {
+        self.font
+            .horizontal_line_metrics(px)
+            .map(|m| m.ascent)
+            .unwrap_or(px)
+    }
+
+    pub fn text_width(&self, text: &str, px: f32) -> i32 {
+        let mut width = 0.0;
+        let mut last = None;
+        for ch in text.chars() {
+            if let Some(prev) = last {
+                width += self.font.horizontal_kern(prev, ch, px).unwrap_or(0.0);
+            }
+            width += self.font.metrics(ch, px).advance_width;
+            last = Some(ch);
+        }
+        width.ceil() as i32
+    }
+
+    fn draw_text<D>(
+        &self,
+        target: &mut D,
+        text: &str,
+        px: f32,
+        left: i32,
+        top: i32,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let mut pixels = Vec::new();
+        let baseline = top as f32 + self.ascent(px);
+        let mut x = left as f32;
+        let mut last = None;
+        for ch in text.chars() {
+            if let Some(prev) = last {
+                x += self.font.horizontal_kern(prev, ch, px).unwrap_or(0.0);
+            }
+            let (metrics, coverage) = self.font.rasterize(ch, px);
+            let glyph_left = (x + metrics.xmin as f32).round() as i32;
+            let glyph_top = (baseline - metrics.ymin as f32).round() as i32 - metrics.height as i32;
+            for (i, &value) in coverage.iter().enumerate() {
+                if value >= THRESHOLD {
+                    let gx = glyph_left + (i % metrics.width) as i32;
+                    let gy = glyph_top + (i / metrics.width) as i32;
+                    pixels.push(Pixel(Point::new(gx, gy), BinaryColor::On));
+                }
+            }
+            x += metrics.advance_width;
+            last = Some(ch);
+        }
+        target.draw_iter(pixels)
+    }
+}
+
+fn text_and_px(line: &Line) -> (&str, f32) {
+    match line {
+        Line::Small(text) => (text, SMALL_PX),
+        Line::Medium(text) => (text, MEDIUM_PX),
+        Line::Large(text) => (text, LARGE_PX),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn renderer() -> &'static Renderer {
+        Renderer::shared().unwrap()
+    }
+
+    #[test]
+    fn lines_stack_from_the_top() {
+        let r = renderer();
+        let lines = [
+            Line::small("Q4 5:32"),
+            Line::large("BOS NYK"),
+            Line::medium("98 97"),
+        ];
+        let layout = r.layout(&lines, WIDTH as i32);
+        let tops: Vec<_> = layout.placed.iter().map(|p| p.top).collect();
+        let small = r.line_height(SMALL_PX);
+        let large = r.line_height(LARGE_PX);
+        let medium = r.line_height(MEDIUM_PX);
+        assert_eq!(tops, vec![TOP, TOP + small, TOP + small + large]);
+        assert_eq!(layout.bottom, TOP + small + large + medium);
+    }
+
+    #[test]
+    fn blank_lines_add_spacing() {
+        let r = renderer();
+        let lines = [Line::small(""), Line::medium("Final")];
+        let layout = r.layout(&lines, WIDTH as i32);
+        assert_eq!(layout.placed.len(), 1);
+        assert_eq!(
+            layout.placed[0].top,
+            TOP + r.line_height(SMALL_PX) + BLANK_SPACING
+        );
+    }
+
+    #[test]
+    fn lines_are_centered() {
+        let r = renderer();
+        let lines = [Line::medium("Celtics"), Line::medium("W")];
+        for placed in r.layout(&lines, WIDTH as i32).placed {
+            let width = r.text_width(placed.text, placed.px);
+            let right = WIDTH as i32 - (placed.left + width);
+            assert!((placed.left - right).abs() <= 1, "{:?}", placed);
+        }
+    }
+
+    #[test]
+    fn cells_spread_across() {
+        let r = renderer();
+        let lines = [Line::small("\u{2022}\u{2022}B\tQ4 5:32\t\u{25b6} \u{2022}")];
+        let placed = r.layout(&lines, WIDTH as i32).placed;
+        assert_eq!(placed.len(), 3);
+        assert_eq!(placed[0].left, MARGIN);
+        let time = r.text_width("Q4 5:32", SMALL_PX);
+        assert_eq!(placed[1].left, WIDTH as i32 / 2 - time / 2);
+        let away = r.text_width(placed[2].text, SMALL_PX);
+        assert_eq!(placed[2].left + away, WIDTH as i32 - MARGIN);
+        assert!(placed.iter().all(|p| p.top == TOP));
+    }
+
+    #[test]
+    fn tables_line_up_in_columns() {
+        let r = renderer();
+        let lines = [
+            Line::small("\tQ1\tQ2\tT"),
+            Line::medium("BOS\t31\t8\t39"),
+            Line::medium("NYK\t7\t112\t119"),
+        ];
+        let layout = r.layout(&lines, WIDTH as i32);
+        let small = r.line_height(SMALL_PX);
+        let medium = r.line_height(MEDIUM_PX);
+        assert_eq!(layout.bottom, TOP + small + medium * 2);
+        let find = |text: &str| layout.placed.iter().find(|p| p.text == text).unwrap();
+        let right = |p: &Placed| p.left + r.text_width(p.text, p.px);
+        // names share a left edge, numbers share a right edge
+        assert_eq!(find("BOS").left, find("NYK").left);
+        assert_eq!(right(find("Q1")), right(find("31")));
+        assert_eq!(right(find("31")), right(find("7")));
+        assert_eq!(right(find("Q2")), right(find("112")));
+        assert_eq!(right(find("T")), right(find("119")));
+        let left = find("BOS").left.min(find("NYK").left);
+        let end = right(find("119"));
+        assert!((left - (WIDTH as i32 - end)).abs() <= 1);
+    }
+
+    #[test]
+    fn a_lone_row_is_spread_not_a_table() {
+        let r = renderer();
+        let lines = [Line::small("a\tb\tc"), Line::small("d\te")];
+        let placed = r.layout(&lines, WIDTH as i32).placed;
+        assert_eq!(placed[0].left, MARGIN);
+        assert_eq!(placed[3].left, MARGIN);
+    }
+
+    #[test]
+    fn render_draws_inside_the_line() {
+        let r = renderer();
+        let bitmap = r.render(&[Line::large("W")]);
+        let black: Vec<_> = (0..HEIGHT)
+            .flat_map(|y| (0..WIDTH).map(move |x| (x, y)))
+            .filter(|&(x, y)| bitmap.get(x, y))
+            .collect();
+        assert!(!black.is_empty());
+        let bottom = (TOP + r.line_height(LARGE_PX)) as u32;
+        assert!(black.iter().all(|&(_, y)| y >= TOP as u32 && y < bottom));
+        let min_x = black.iter().map(|p| p.0).min().unwrap() as i32;
+        let max_x = black.iter().map(|p| p.0).max().unwrap() as i32;
+        let (left, right) = (min_x, WIDTH as i32 - 1 - max_x);
+        assert!((left - right).abs() <= 3, "{} {}", left, right);
+    }
+
+    #[test]
+    fn png_round_trip_size() {
+        let mut bitmap = Bitmap::new(10, 2);
+        bitmap.set(9, 1, true);
+        assert_eq!(bitmap.stride(), 2);
+        assert_eq!(bitmap.as_bytes(), &[0, 0, 0, 0x40]);
+        assert!(bitmap.to_png().unwrap().starts_with(b"\x89PNG"));
+    }
+}
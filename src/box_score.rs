@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
-use serde_json::{Map, Value};
+use serde_json::Value;
+
+use crate::clock::GameClock;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -7,289 +9,1507 @@ pub struct GameBoxScores {
     pub id: String,
     pub home: TeamBoxScore,
     pub away: TeamBoxScore,
+    #[serde(default)]
+    pub officials: Vec<Official>,
 }
 
 impl GameBoxScores {
-    pub fn try_from_obj(obj: &Map<String, Value>) -> Option<Self> {
-        let game = obj.get("game")?.as_object()?;
-        let id = game.get("gameId")?.as_str()?;
-        let home_team = game.get("homeTeam")?.as_object()?;
-        let home = TeamBoxScore::try_from_obj(home_team)?;
-        let away_team = game.get("awayTeam")?.as_object()?;
-        let away = TeamBoxScore::try_from_obj(away_team)?;
-
-        Some(Self {
-            id: id.to_string(),
-            home: home,
-            away: away,
-        })
+    /// Parse the body of a `boxscore_{game_id}.json` request
+    pub fn from_json(json: &str) -> Result<Self, BoxScoreError> {
+        let payload: BoxScorePayload = serde_json::from_str(json).map_err(BoxScoreError::Json)?;
+        Ok(Self::from(payload.game))
+    }
+}
+
+impl GameBoxScores {
+    /// The first player on either team matching `query`, see
+    /// [`StatPlayer::matches`]
+    pub fn find_player(&self, query: &str) -> Option<&StatPlayer> {
+        self.home
+            .players
+            .iter()
+            .chain(self.away.players.iter())
+            .find(|p| p.matches(query))
     }
 }
 
+impl From<BoxScoreGame> for GameBoxScores {
+    fn from(game: BoxScoreGame) -> Self {
+        Self {
+            id: game.game_id,
+            home: TeamBoxScore::from(game.home_team),
+            away: TeamBoxScore::from(game.away_team),
+            officials: game.officials,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum BoxScoreError {
+    /// The payload wasn't JSON or was missing the game or a team
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for BoxScoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "invalid box score: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for BoxScoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Json(e) => Some(e),
+        }
+    }
+}
+
+/// The box score feed as it is sent, only the game and
+/// its two teams are required
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxScorePayload {
+    pub game: BoxScoreGame,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxScoreGame {
+    pub game_id: String,
+    #[serde(default, deserialize_with = "lenient")]
+    pub game_status: u8,
+    #[serde(default)]
+    pub game_status_text: String,
+    #[serde(default, deserialize_with = "lenient")]
+    pub period: u8,
+    pub home_team: BoxScoreTeam,
+    pub away_team: BoxScoreTeam,
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub officials: Vec<Official>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxScoreTeam {
+    #[serde(default, deserialize_with = "lenient")]
+    pub team_id: u64,
+    #[serde(default)]
+    pub team_name: String,
+    #[serde(default)]
+    pub team_city: String,
+    pub team_tricode: String,
+    #[serde(default, deserialize_with = "lenient")]
+    pub score: u16,
+    #[serde(default, deserialize_with = "lenient_vec")]
+    pub players: Vec<StatPlayer>,
+    #[serde(default)]
+    pub statistics: TeamStats,
+}
+
+/// The team totals the feed sends alongside the players, the
+/// same columns as a player plus the ones that only make sense
+/// for a team
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamStats {
+    #[serde(flatten)]
+    pub totals: Stats,
+    #[serde(default, deserialize_with = "lenient")]
+    pub bench_points: u8,
+    #[serde(default, deserialize_with = "lenient")]
+    pub points_from_turnovers: u8,
+    #[serde(default, deserialize_with = "lenient")]
+    pub biggest_lead: u8,
+    #[serde(default, deserialize_with = "lenient")]
+    pub biggest_scoring_run: u8,
+    #[serde(default, deserialize_with = "lenient")]
+    pub lead_changes: u8,
+    #[serde(default, deserialize_with = "lenient")]
+    pub times_tied: u8,
+    /// Rebounds credited to the team rather than a player
+    #[serde(default, deserialize_with = "lenient")]
+    pub rebounds_team: u8,
+    /// Player and team turnovers
+    #[serde(default, deserialize_with = "lenient")]
+    pub turnovers_total: u8,
+}
+
+impl TeamStats {
+    pub fn turnovers(&self) -> u8 {
+        self.turnovers_total.max(self.totals.turnovers)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Official {
+    #[serde(default, deserialize_with = "lenient")]
+    pub person_id: u64,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub name_i: String,
+    #[serde(default)]
+    pub jersey_num: String,
+    /// e.g. `OFFICIAL1`
+    #[serde(default)]
+    pub assignment: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct TeamBoxScore {
     pub abv: String,
     pub box_score: BoxScore,
+    pub players: Vec<StatPlayer>,
+    #[serde(default)]
+    pub statistics: TeamStats,
+}
+
+impl From<BoxScoreTeam> for TeamBoxScore {
+    fn from(team: BoxScoreTeam) -> Self {
+        Self {
+            abv: team.team_tricode,
+            box_score: BoxScore::from_players(&team.players),
+            players: team.players,
+            statistics: team.statistics,
+        }
+    }
 }
 
 impl TeamBoxScore {
-    pub fn try_from_obj(obj: &Map<String, Value>) -> Option<Self> {
-        let players = obj.get("players")?;
-        let box_score = BoxScore::try_from_value(players.clone())?;
-        let abv = obj.get("teamTricode")?.as_str()?;
-        Some(Self {
-            abv: abv.to_string(),
-            box_score,
-        })
+    /// The team's totals summed from its players
+    pub fn totals(&self) -> Stats {
+        Stats::total(self.players.iter().map(|p| &p.statistics))
+    }
+
+    pub fn starters(&self) -> impl Iterator<Item = &StatPlayer> {
+        self.players.iter().filter(|p| p.starter)
+    }
+
+    pub fn bench(&self) -> impl Iterator<Item = &StatPlayer> {
+        self.players.iter().filter(|p| !p.starter)
+    }
+
+    pub fn inactive(&self) -> impl Iterator<Item = &StatPlayer> {
+        self.players.iter().filter(|p| p.is_inactive())
+    }
+
+    /// Points scored by the starters and by the bench
+    pub fn points_split(&self) -> (u16, u16) {
+        let points = |players: &mut dyn Iterator<Item = &StatPlayer>| {
+            players.map(|p| u16::from(p.statistics.points)).sum()
+        };
+        (points(&mut self.starters()), points(&mut self.bench()))
+    }
+
+    /// The order of a printed box score, the starters followed
+    /// by the bench players who got in the game, most minutes first
+    pub fn table_order(&self) -> Vec<&StatPlayer> {
+        let mut bench: Vec<_> = self
+            .players
+            .iter()
+            .filter(|p| !p.starter && p.has_played())
+            .collect();
+        bench.sort_by(|lhs, rhs| {
+            rhs.statistics
+                .minutes_played()
+                .partial_cmp(&lhs.statistics.minutes_played())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        self.starters().chain(bench).collect()
     }
 }
 
+/// The players ranked in every [`StatCategory`]
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct BoxScore {
-    pub assist: Option<TopPlayerByValue>,
-    pub blocks: Option<TopPlayerByValue>,
-    pub fouled: Option<TopPlayerByValue>,
-    pub fouler: Option<TopPlayerByValue>,
-    pub steals: Option<TopPlayerByValue>,
-    pub turnovers: Option<TopPlayerByValue>,
-    pub points: Option<TopPlayerByValue>,
-    pub paint_points: Option<TopPlayerByValue>,
-    pub threes: Option<TopPlayerByValue>,
-    pub rebounds: Option<TopPlayerByValue>,
-    pub off_rebounds: Option<TopPlayerByValue>,
-    pub def_rebounds: Option<TopPlayerByValue>,
+    pub leaders: Vec<CategoryLeader>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryLeader {
+    pub category: StatCategory,
+    /// Highest value first, players with the same value share a rank
+    pub ranks: Vec<Rank>,
+}
+
+/// Every player with the same value in a category
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Rank {
+    pub names: Vec<String>,
+    pub value: f32,
+}
+
+impl Rank {
+    pub fn is_tie(&self) -> bool {
+        self.names.len() > 1
+    }
 }
 
 impl BoxScore {
-    pub fn try_from_value(value: Value) -> Option<Self> {
-        let stats: Vec<StatPlayer> = serde_json::from_value(value.clone())
-            .map_err(|e| {
-                std::fs::write(
-                    "box_score_calc_err.json",
-                    serde_json::to_string_pretty(&value).unwrap(),
-                )
-                .unwrap();
-                e
+    pub fn from_players(players: &[StatPlayer]) -> Self {
+        let leaders = StatCategory::ALL
+            .iter()
+            .filter_map(|&category| {
+                let ranks = category.rank(players);
+                if ranks.is_empty() {
+                    return None;
+                }
+                Some(CategoryLeader { category, ranks })
             })
-            .unwrap();
-        let mut ret = BoxScore::default();
-        for stat in stats {
-            ret.update_assist(stat.player_name.clone(), stat.statistics.assists);
-            ret.update_blocks(stat.player_name.clone(), stat.statistics.blocks);
-            ret.update_fouled(stat.player_name.clone(), stat.statistics.fouls_drawn);
-            ret.update_fouler(stat.player_name.clone(), stat.statistics.fouls_personal);
-            ret.update_steals(stat.player_name.clone(), stat.statistics.steals);
-            ret.update_turnovers(stat.player_name.clone(), stat.statistics.turnovers);
-            ret.update_points(stat.player_name.clone(), stat.statistics.points);
-            ret.update_paint_points(
-                stat.player_name.clone(),
-                stat.statistics.points_in_the_paint,
-            );
-            ret.update_threes(
-                stat.player_name.clone(),
-                stat.statistics.three_pointers_made,
-            );
-            ret.update_rebounds(stat.player_name.clone(), stat.statistics.rebounds_total);
-            ret.update_off_rebounds(stat.player_name.clone(), stat.statistics.rebounds_offensive);
-            ret.update_def_rebounds(stat.player_name.clone(), stat.statistics.rebounds_defensive);
-        }
-        Some(ret)
+            .collect();
+        Self { leaders }
     }
 
-    fn update_assist(&mut self, name: String, value: u8) {
-        if let Some(v) = self.assist.as_mut() {
-            if v.value < value {
-                *v = TopPlayerByValue { name, value };
-            }
-        } else {
-            self.assist = Some(TopPlayerByValue { name, value })
-        }
+    /// The top rank in `category`, more than one player if tied
+    pub fn leader(&self, category: StatCategory) -> Option<&Rank> {
+        self.leaders(category, 1).first()
     }
-    fn update_blocks(&mut self, name: String, value: u8) {
-        if let Some(v) = self.blocks.as_mut() {
-            if v.value < value {
-                *v = TopPlayerByValue { name, value };
-            }
+
+    /// The fewest ranks that cover the top `n` players in `category`,
+    /// a tie at the last rank can make this more than `n` players
+    pub fn leaders(&self, category: StatCategory, n: usize) -> &[Rank] {
+        let ranks = if let Some(l) = self.leaders.iter().find(|l| l.category == category) {
+            &l.ranks
         } else {
-            self.blocks = Some(TopPlayerByValue { name, value })
-        }
+            return &[];
+        };
+        let mut players = 0;
+        let end = ranks
+            .iter()
+            .position(|rank| {
+                players += rank.names.len();
+                players >= n
+            })
+            .map(|idx| idx + 1)
+            .unwrap_or(ranks.len());
+        &ranks[..end]
     }
-    fn update_fouled(&mut self, name: String, value: u8) {
-        if let Some(v) = self.fouled.as_mut() {
-            if v.value < value {
-                *v = TopPlayerByValue { name, value };
-            }
-        } else {
-            self.fouled = Some(TopPlayerByValue { name, value })
+}
+
+/// A column of the box score that players can be ranked by
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum StatCategory {
+    Assists,
+    Blocks,
+    BlocksReceived,
+    FieldGoalsAttempted,
+    FieldGoalsMade,
+    FieldGoalsPercentage,
+    FoulsDrawn,
+    FoulsOffensive,
+    FoulsPersonal,
+    FoulsTechnical,
+    FreeThrowsAttempted,
+    FreeThrowsMade,
+    FreeThrowsPercentage,
+    Minus,
+    Minutes,
+    Plus,
+    PlusMinus,
+    Points,
+    FastBreakPoints,
+    PaintPoints,
+    SecondChancePoints,
+    DefensiveRebounds,
+    OffensiveRebounds,
+    Rebounds,
+    Steals,
+    ThreesAttempted,
+    Threes,
+    ThreesPercentage,
+    Turnovers,
+    TwosAttempted,
+    TwosMade,
+    TwosPercentage,
+    TrueShooting,
+    EffectiveFieldGoal,
+    GameScore,
+    Efficiency,
+    Usage,
+    AssistToTurnover,
+    DoubleDigits,
+    PointsPer36,
+    ReboundsPer36,
+    AssistsPer36,
+}
+
+impl StatCategory {
+    pub const ALL: [StatCategory; 42] = [
+        StatCategory::Assists,
+        StatCategory::Blocks,
+        StatCategory::BlocksReceived,
+        StatCategory::FieldGoalsAttempted,
+        StatCategory::FieldGoalsMade,
+        StatCategory::FieldGoalsPercentage,
+        StatCategory::FoulsDrawn,
+        StatCategory::FoulsOffensive,
+        StatCategory::FoulsPersonal,
+        StatCategory::FoulsTechnical,
+        StatCategory::FreeThrowsAttempted,
+        StatCategory::FreeThrowsMade,
+        StatCategory::FreeThrowsPercentage,
+        StatCategory::Minus,
+        StatCategory::Minutes,
+        StatCategory::Plus,
+        StatCategory::PlusMinus,
+        StatCategory::Points,
+        StatCategory::FastBreakPoints,
+        StatCategory::PaintPoints,
+        StatCategory::SecondChancePoints,
+        StatCategory::DefensiveRebounds,
+        StatCategory::OffensiveRebounds,
+        StatCategory::Rebounds,
+        StatCategory::Steals,
+        StatCategory::ThreesAttempted,
+        StatCategory::Threes,
+        StatCategory::ThreesPercentage,
+        StatCategory::Turnovers,
+        StatCategory::TwosAttempted,
+        StatCategory::TwosMade,
+        StatCategory::TwosPercentage,
+        StatCategory::TrueShooting,
+        StatCategory::EffectiveFieldGoal,
+        StatCategory::GameScore,
+        StatCategory::Efficiency,
+        StatCategory::Usage,
+        StatCategory::AssistToTurnover,
+        StatCategory::DoubleDigits,
+        StatCategory::PointsPer36,
+        StatCategory::ReboundsPer36,
+        StatCategory::AssistsPer36,
+    ];
+
+    /// The categories shown after a game when none are configured
+    pub const DEFAULT_ROTATION: [StatCategory; 12] = [
+        StatCategory::Assists,
+        StatCategory::Blocks,
+        StatCategory::FoulsDrawn,
+        StatCategory::FoulsPersonal,
+        StatCategory::Steals,
+        StatCategory::Turnovers,
+        StatCategory::Points,
+        StatCategory::PaintPoints,
+        StatCategory::Threes,
+        StatCategory::Rebounds,
+        StatCategory::OffensiveRebounds,
+        StatCategory::DefensiveRebounds,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatCategory::Assists => "assists",
+            StatCategory::Blocks => "blocks",
+            StatCategory::BlocksReceived => "blocksReceived",
+            StatCategory::FieldGoalsAttempted => "fieldGoalsAttempted",
+            StatCategory::FieldGoalsMade => "fieldGoalsMade",
+            StatCategory::FieldGoalsPercentage => "fieldGoalsPercentage",
+            StatCategory::FoulsDrawn => "foulsDrawn",
+            StatCategory::FoulsOffensive => "foulsOffensive",
+            StatCategory::FoulsPersonal => "foulsPersonal",
+            StatCategory::FoulsTechnical => "foulsTechnical",
+            StatCategory::FreeThrowsAttempted => "freeThrowsAttempted",
+            StatCategory::FreeThrowsMade => "freeThrowsMade",
+            StatCategory::FreeThrowsPercentage => "freeThrowsPercentage",
+            StatCategory::Minus => "minus",
+            StatCategory::Minutes => "minutes",
+            StatCategory::Plus => "plus",
+            StatCategory::PlusMinus => "plusMinus",
+            StatCategory::Points => "points",
+            StatCategory::FastBreakPoints => "fastBreakPoints",
+            StatCategory::PaintPoints => "paintPoints",
+            StatCategory::SecondChancePoints => "secondChancePoints",
+            StatCategory::DefensiveRebounds => "defensiveRebounds",
+            StatCategory::OffensiveRebounds => "offensiveRebounds",
+            StatCategory::Rebounds => "rebounds",
+            StatCategory::Steals => "steals",
+            StatCategory::ThreesAttempted => "threesAttempted",
+            StatCategory::Threes => "threes",
+            StatCategory::ThreesPercentage => "threesPercentage",
+            StatCategory::Turnovers => "turnovers",
+            StatCategory::TwosAttempted => "twosAttempted",
+            StatCategory::TwosMade => "twosMade",
+            StatCategory::TwosPercentage => "twosPercentage",
+            StatCategory::TrueShooting => "trueShooting",
+            StatCategory::EffectiveFieldGoal => "effectiveFieldGoal",
+            StatCategory::GameScore => "gameScore",
+            StatCategory::Efficiency => "efficiency",
+            StatCategory::Usage => "usage",
+            StatCategory::AssistToTurnover => "assistToTurnover",
+            StatCategory::DoubleDigits => "doubleDigits",
+            StatCategory::PointsPer36 => "pointsPer36",
+            StatCategory::ReboundsPer36 => "reboundsPer36",
+            StatCategory::AssistsPer36 => "assistsPer36",
         }
     }
-    fn update_fouler(&mut self, name: String, value: u8) {
-        if let Some(v) = self.fouler.as_mut() {
-            if v.value < value {
-                *v = TopPlayerByValue { name, value };
-            }
-        } else {
-            self.fouler = Some(TopPlayerByValue { name, value })
+
+    /// The short label used on the display
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatCategory::Assists => "Assists",
+            StatCategory::Blocks => "Blocks",
+            StatCategory::BlocksReceived => "Blocked",
+            StatCategory::FieldGoalsAttempted => "FGA",
+            StatCategory::FieldGoalsMade => "FGM",
+            StatCategory::FieldGoalsPercentage => "FG%",
+            StatCategory::FoulsDrawn => "Fouled",
+            StatCategory::FoulsOffensive => "Off Fouls",
+            StatCategory::FoulsPersonal => "Fouler",
+            StatCategory::FoulsTechnical => "Techs",
+            StatCategory::FreeThrowsAttempted => "FTA",
+            StatCategory::FreeThrowsMade => "FTM",
+            StatCategory::FreeThrowsPercentage => "FT%",
+            StatCategory::Minus => "Minus",
+            StatCategory::Minutes => "Minutes",
+            StatCategory::Plus => "Plus",
+            StatCategory::PlusMinus => "+/-",
+            StatCategory::Points => "Points",
+            StatCategory::FastBreakPoints => "Fast Break",
+            StatCategory::PaintPoints => "Paint Pts",
+            StatCategory::SecondChancePoints => "2nd Chance",
+            StatCategory::DefensiveRebounds => "Rebounds(d)",
+            StatCategory::OffensiveRebounds => "Rebounds(o)",
+            StatCategory::Rebounds => "Rebounds(*)",
+            StatCategory::Steals => "Steals",
+            StatCategory::ThreesAttempted => "3PA",
+            StatCategory::Threes => "Threes",
+            StatCategory::ThreesPercentage => "3P%",
+            StatCategory::Turnovers => "Turnovers",
+            StatCategory::TwosAttempted => "2PA",
+            StatCategory::TwosMade => "2PM",
+            StatCategory::TwosPercentage => "2P%",
+            StatCategory::TrueShooting => "TS%",
+            StatCategory::EffectiveFieldGoal => "eFG%",
+            StatCategory::GameScore => "Game Score",
+            StatCategory::Efficiency => "EFF",
+            StatCategory::Usage => "USG%",
+            StatCategory::AssistToTurnover => "AST/TO",
+            StatCategory::DoubleDigits => "Dbl-Dbl",
+            StatCategory::PointsPer36 => "Pts/36",
+            StatCategory::ReboundsPer36 => "Reb/36",
+            StatCategory::AssistsPer36 => "Ast/36",
         }
     }
-    fn update_steals(&mut self, name: String, value: u8) {
-        if let Some(v) = self.steals.as_mut() {
-            if v.value < value {
-                *v = TopPlayerByValue { name, value };
-            }
-        } else {
-            self.steals = Some(TopPlayerByValue { name, value })
+
+    /// The box score column heading, short enough to share a line
+    /// with both teams' leaders
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            StatCategory::Assists => "AST",
+            StatCategory::Blocks => "BLK",
+            StatCategory::BlocksReceived => "BLKA",
+            StatCategory::FieldGoalsAttempted => "FGA",
+            StatCategory::FieldGoalsMade => "FGM",
+            StatCategory::FieldGoalsPercentage => "FG%",
+            StatCategory::FoulsDrawn => "PFD",
+            StatCategory::FoulsOffensive => "OF",
+            StatCategory::FoulsPersonal => "PF",
+            StatCategory::FoulsTechnical => "TF",
+            StatCategory::FreeThrowsAttempted => "FTA",
+            StatCategory::FreeThrowsMade => "FTM",
+            StatCategory::FreeThrowsPercentage => "FT%",
+            StatCategory::Minus => "-",
+            StatCategory::Minutes => "MIN",
+            StatCategory::Plus => "+",
+            StatCategory::PlusMinus => "+/-",
+            StatCategory::Points => "PTS",
+            StatCategory::FastBreakPoints => "FBP",
+            StatCategory::PaintPoints => "PITP",
+            StatCategory::SecondChancePoints => "2CP",
+            StatCategory::DefensiveRebounds => "DREB",
+            StatCategory::OffensiveRebounds => "OREB",
+            StatCategory::Rebounds => "REB",
+            StatCategory::Steals => "STL",
+            StatCategory::ThreesAttempted => "3PA",
+            StatCategory::Threes => "3PM",
+            StatCategory::ThreesPercentage => "3P%",
+            StatCategory::Turnovers => "TO",
+            StatCategory::TwosAttempted => "2PA",
+            StatCategory::TwosMade => "2PM",
+            StatCategory::TwosPercentage => "2P%",
+            StatCategory::TrueShooting => "TS%",
+            StatCategory::EffectiveFieldGoal => "eFG%",
+            StatCategory::GameScore => "GmSc",
+            StatCategory::Efficiency => "EFF",
+            StatCategory::Usage => "USG%",
+            StatCategory::AssistToTurnover => "A/TO",
+            StatCategory::DoubleDigits => "DD",
+            StatCategory::PointsPer36 => "P/36",
+            StatCategory::ReboundsPer36 => "R/36",
+            StatCategory::AssistsPer36 => "A/36",
         }
     }
-    fn update_turnovers(&mut self, name: String, value: u8) {
-        if let Some(v) = self.turnovers.as_mut() {
-            if v.value < value {
-                *v = TopPlayerByValue { name, value };
-            }
-        } else {
-            self.turnovers = Some(TopPlayerByValue { name, value })
-        }
+
+    /// This category's value for `stats`, [`StatCategory::Usage`] is
+    /// relative to the team and is `0` without the team's totals
+    pub fn value(&self, stats: &Stats) -> f32 {
+        self.value_with_team(stats, None)
     }
-    fn update_points(&mut self, name: String, value: u8) {
-        if let Some(v) = self.points.as_mut() {
-            if v.value < value {
-                *v = TopPlayerByValue { name, value };
-            }
-        } else {
-            self.points = Some(TopPlayerByValue { name, value })
+
+    pub fn value_with_team(&self, stats: &Stats, team: Option<&Stats>) -> f32 {
+        match self {
+            StatCategory::Assists => stats.assists.into(),
+            StatCategory::Blocks => stats.blocks.into(),
+            StatCategory::BlocksReceived => stats.blocks_received.into(),
+            StatCategory::FieldGoalsAttempted => stats.field_goals_attempted.into(),
+            StatCategory::FieldGoalsMade => stats.field_goals_made.into(),
+            StatCategory::FieldGoalsPercentage => stats.field_goals_percentage,
+            StatCategory::FoulsDrawn => stats.fouls_drawn.into(),
+            StatCategory::FoulsOffensive => stats.fouls_offensive.into(),
+            StatCategory::FoulsPersonal => stats.fouls_personal.into(),
+            StatCategory::FoulsTechnical => stats.fouls_technical.into(),
+            StatCategory::FreeThrowsAttempted => stats.free_throws_attempted.into(),
+            StatCategory::FreeThrowsMade => stats.free_throws_made.into(),
+            StatCategory::FreeThrowsPercentage => stats.free_throws_percentage,
+            StatCategory::Minus => stats.minus,
+            StatCategory::Minutes => stats.minutes_played(),
+            StatCategory::Plus => stats.plus,
+            StatCategory::PlusMinus => stats.plus_minus_points,
+            StatCategory::Points => stats.points.into(),
+            StatCategory::FastBreakPoints => stats.points_fast_break.into(),
+            StatCategory::PaintPoints => stats.points_in_the_paint.into(),
+            StatCategory::SecondChancePoints => stats.points_second_chance.into(),
+            StatCategory::DefensiveRebounds => stats.rebounds_defensive.into(),
+            StatCategory::OffensiveRebounds => stats.rebounds_offensive.into(),
+            StatCategory::Rebounds => stats.rebounds_total.into(),
+            StatCategory::Steals => stats.steals.into(),
+            StatCategory::ThreesAttempted => stats.three_pointers_attempted.into(),
+            StatCategory::Threes => stats.three_pointers_made.into(),
+            StatCategory::ThreesPercentage => stats.three_pointers_percentage,
+            StatCategory::Turnovers => stats.turnovers.into(),
+            StatCategory::TwosAttempted => stats.two_pointers_attempted.into(),
+            StatCategory::TwosMade => stats.two_pointers_made.into(),
+            StatCategory::TwosPercentage => stats.two_pointers_percentage,
+            StatCategory::TrueShooting => stats.true_shooting().unwrap_or(0.0),
+            StatCategory::EffectiveFieldGoal => stats.effective_field_goal().unwrap_or(0.0),
+            StatCategory::GameScore => stats.game_score(),
+            StatCategory::Efficiency => stats.efficiency(),
+            StatCategory::Usage => team.and_then(|t| stats.usage(t)).unwrap_or(0.0),
+            StatCategory::AssistToTurnover => stats.assist_to_turnover(),
+            StatCategory::DoubleDigits => match stats.double_digit_categories() {
+                n if n >= 2 => n.into(),
+                _ => 0.0,
+            },
+            StatCategory::PointsPer36 => stats.per_36(stats.points).unwrap_or(0.0),
+            StatCategory::ReboundsPer36 => stats.per_36(stats.rebounds_total).unwrap_or(0.0),
+            StatCategory::AssistsPer36 => stats.per_36(stats.assists).unwrap_or(0.0),
         }
     }
-    fn update_paint_points(&mut self, name: String, value: u8) {
-        if let Some(v) = self.paint_points.as_mut() {
-            if v.value < value {
-                *v = TopPlayerByValue { name, value };
+
+    /// Format a value of this category for display, percentages are
+    /// stored as fractions and minutes as decimal minutes
+    pub fn format_value(&self, value: f32) -> String {
+        match self {
+            StatCategory::FieldGoalsPercentage
+            | StatCategory::FreeThrowsPercentage
+            | StatCategory::ThreesPercentage
+            | StatCategory::TwosPercentage
+            | StatCategory::TrueShooting
+            | StatCategory::EffectiveFieldGoal
+            | StatCategory::Usage => format!("{:.1}%", value * 100.0),
+            StatCategory::GameScore
+            | StatCategory::Efficiency
+            | StatCategory::AssistToTurnover
+            | StatCategory::PointsPer36
+            | StatCategory::ReboundsPer36
+            | StatCategory::AssistsPer36 => format!("{:.1}", value),
+            StatCategory::DoubleDigits => match value as u8 {
+                2 => "double-double".to_string(),
+                3 => "triple-double".to_string(),
+                4 => "quadruple-double".to_string(),
+                _ => format!("{}", value),
+            },
+            StatCategory::Minutes => {
+                let secs = (value * 60.0).round() as u32;
+                format!("{}:{:02}", secs / 60, secs % 60)
             }
-        } else {
-            self.paint_points = Some(TopPlayerByValue { name, value })
+            StatCategory::Plus | StatCategory::Minus | StatCategory::PlusMinus => {
+                format!("{:+}", value.round() as i32)
+            }
+            _ => format!("{}", value.round() as i32),
         }
     }
-    fn update_threes(&mut self, name: String, value: u8) {
-        if let Some(v) = self.threes.as_mut() {
-            if v.value < value {
-                *v = TopPlayerByValue { name, value };
-            }
-        } else {
-            self.threes = Some(TopPlayerByValue { name, value })
+
+    /// If negative values are meaningful for this category, for
+    /// the rest a zero means the player didn't record the stat
+    pub fn is_signed(&self) -> bool {
+        matches!(
+            self,
+            StatCategory::Plus
+                | StatCategory::Minus
+                | StatCategory::PlusMinus
+                | StatCategory::GameScore
+                | StatCategory::Efficiency
+        )
+    }
+
+    /// The shots a player needs to be ranked in a percentage
+    /// category, so a bench player who went 1 for 1 doesn't lead
+    pub fn min_attempts(&self) -> u16 {
+        match self {
+            StatCategory::FieldGoalsPercentage
+            | StatCategory::TwosPercentage
+            | StatCategory::EffectiveFieldGoal
+            | StatCategory::TrueShooting => 5,
+            StatCategory::ThreesPercentage => 3,
+            StatCategory::FreeThrowsPercentage => 2,
+            _ => 0,
         }
     }
-    fn update_rebounds(&mut self, name: String, value: u8) {
-        if let Some(v) = self.rebounds.as_mut() {
-            if v.value < value {
-                *v = TopPlayerByValue { name, value };
+
+    /// The shots counted against [`StatCategory::min_attempts`]
+    fn attempts(&self, stats: &Stats) -> u16 {
+        match self {
+            StatCategory::FieldGoalsPercentage | StatCategory::EffectiveFieldGoal => {
+                stats.field_goals_attempted.into()
             }
-        } else {
-            self.rebounds = Some(TopPlayerByValue { name, value })
+            StatCategory::TwosPercentage => stats.two_pointers_attempted.into(),
+            StatCategory::ThreesPercentage => stats.three_pointers_attempted.into(),
+            StatCategory::FreeThrowsPercentage => stats.free_throws_attempted.into(),
+            StatCategory::TrueShooting => {
+                u16::from(stats.field_goals_attempted) + u16::from(stats.free_throws_attempted)
+            }
+            _ => 0,
         }
     }
-    fn update_off_rebounds(&mut self, name: String, value: u8) {
-        if let Some(v) = self.off_rebounds.as_mut() {
-            if v.value < value {
-                *v = TopPlayerByValue { name, value };
+
+    /// If `lhs` and `rhs` tie in this category, values that display
+    /// the same are a tie so a ranking never shows two players with
+    /// the same value at different ranks
+    pub fn is_tie(&self, lhs: f32, rhs: f32) -> bool {
+        self.format_value(lhs) == self.format_value(rhs)
+    }
+
+    /// Group `players` by their value in this category, highest
+    /// first, see [`StatCategory::is_tie`]
+    pub fn rank(&self, players: &[StatPlayer]) -> Vec<Rank> {
+        let team = Stats::total(players.iter().map(|p| &p.statistics));
+        let mut values: Vec<_> = players
+            .iter()
+            .filter(|p| self.attempts(&p.statistics) >= self.min_attempts())
+            .map(|p| {
+                (
+                    self.value_with_team(&p.statistics, Some(&team)),
+                    &p.player_name,
+                )
+            })
+            .filter(|(value, _)| self.is_signed() || *value > 0.0)
+            .collect();
+        values.sort_by(|(lhs, _), (rhs, _)| {
+            rhs.partial_cmp(lhs).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let mut ret: Vec<Rank> = Vec::new();
+        for (value, name) in values {
+            match ret.last_mut() {
+                Some(rank) if self.is_tie(rank.value, value) => rank.names.push(name.clone()),
+                _ => ret.push(Rank {
+                    names: vec![name.clone()],
+                    value,
+                }),
             }
-        } else {
-            self.off_rebounds = Some(TopPlayerByValue { name, value })
         }
+        ret
+    }
+}
+
+impl std::str::FromStr for StatCategory {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|cat| cat.name().eq_ignore_ascii_case(s))
+            .copied()
+            .or_else(|| {
+                Self::ALL
+                    .iter()
+                    .find(|cat| cat.abbreviation().eq_ignore_ascii_case(s))
+                    .copied()
+            })
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(StatCategory::name).collect();
+                format!(
+                    "unknown stat category {:?}, expected one of {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// A category in the rotation and how many of its leaders to
+/// list, written `points:3` or `pts:3` with the count defaulting
+/// to one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RotationEntry {
+    pub category: StatCategory,
+    pub top: usize,
+}
+
+impl From<StatCategory> for RotationEntry {
+    fn from(category: StatCategory) -> Self {
+        Self { category, top: 1 }
     }
-    fn update_def_rebounds(&mut self, name: String, value: u8) {
-        if let Some(v) = self.def_rebounds.as_mut() {
-            if v.value < value {
-                *v = TopPlayerByValue { name, value };
+}
+
+impl std::str::FromStr for RotationEntry {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (category, top) = match s.split_once(':') {
+            Some((category, top)) => {
+                let top = top
+                    .trim()
+                    .parse()
+                    .ok()
+                    .filter(|&top| top > 0)
+                    .ok_or_else(|| format!("invalid number of leaders {:?}", top))?;
+                (category, top)
             }
-        } else {
-            self.def_rebounds = Some(TopPlayerByValue { name, value })
-        }
+            None => (s, 1),
+        };
+        Ok(Self {
+            category: category.trim().parse()?,
+            top,
+        })
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
-pub struct TopPlayerByValue {
-    pub name: String,
-    pub value: u8,
+pub struct StatPlayer {
+    #[serde(default, deserialize_with = "lenient")]
+    pub person_id: u64,
+    #[serde(alias = "nameI")]
+    pub player_name: String,
+    #[serde(default)]
+    pub first_name: String,
+    #[serde(default)]
+    pub family_name: String,
+    #[serde(default, deserialize_with = "deserialize_flag")]
+    pub starter: bool,
+    /// If the player is on the floor as of this box score
+    #[serde(default, rename = "oncourt", deserialize_with = "deserialize_flag")]
+    pub on_court: bool,
+    #[serde(default, deserialize_with = "deserialize_status")]
+    pub status: PlayerStatus,
+    /// e.g. `INACTIVE_INJURY` or `DNP_COACH`
+    #[serde(default)]
+    pub not_playing_reason: Option<String>,
+    /// e.g. `Left Ankle; Sprain`
+    #[serde(default)]
+    pub not_playing_description: Option<String>,
+    #[serde(default)]
+    pub statistics: Stats,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+impl StatPlayer {
+    pub fn has_played(&self) -> bool {
+        self.statistics.minutes_played() > 0.0
+    }
+
+    /// If `query` is this player's box score name, full name, family
+    /// name or person id, ignoring case
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim();
+        let full_name = format!("{} {}", self.first_name, self.family_name);
+        [self.player_name.as_str(), &full_name, &self.family_name]
+            .iter()
+            .any(|name| !name.trim().is_empty() && name.trim().eq_ignore_ascii_case(query))
+            || query.parse() == Ok(self.person_id)
+    }
+
+    pub fn is_inactive(&self) -> bool {
+        matches!(self.status, PlayerStatus::Inactive)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "camelCase")]
-struct StatPlayer {
-    #[serde(alias = "nameI")]
-    player_name: String,
-    statistics: Stats,
+pub enum PlayerStatus {
+    #[default]
+    Active,
+    Inactive,
+    Other(String),
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+impl From<&str> for PlayerStatus {
+    fn from(s: &str) -> Self {
+        match s.to_ascii_lowercase().as_str() {
+            "active" => Self::Active,
+            "inactive" => Self::Inactive,
+            _ => Self::Other(s.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for PlayerStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Active => "Active".fmt(f),
+            Self::Inactive => "Inactive".fmt(f),
+            Self::Other(s) => s.fmt(f),
+        }
+    }
+}
+
+/// The box score sends the status as `"ACTIVE"`/`"INACTIVE"`
+fn deserialize_status<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<PlayerStatus, D::Error> {
+    Ok(match Value::deserialize(deserializer)? {
+        Value::String(s) => PlayerStatus::from(s.as_str()),
+        _ => PlayerStatus::default(),
+    })
+}
+
+/// Accept numbers sent as strings and treat `null` or anything
+/// that doesn't parse as the default, one odd column shouldn't
+/// lose the rest of a player's line
+fn lenient<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: std::str::FromStr + Default,
+{
+    let value = Value::deserialize(deserializer)?;
+    let parsed = match &value {
+        Value::Null => return Ok(T::default()),
+        Value::String(s) if s.is_empty() => return Ok(T::default()),
+        Value::String(s) => s.trim().parse().ok(),
+        Value::Number(n) => n.to_string().parse().ok(),
+        Value::Bool(b) => u8::from(*b).to_string().parse().ok(),
+        _ => None,
+    };
+    Ok(parsed.unwrap_or_else(|| {
+        log::warn!("unexpected box score value {}", value);
+        T::default()
+    }))
+}
+
+/// Parse each entry on its own, skipping the ones that fail
+/// instead of failing the whole list
+fn lenient_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let values: Option<Vec<Value>> = Deserialize::deserialize(deserializer)?;
+    Ok(values
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|value| {
+            serde_json::from_value(value)
+                .map_err(|e| log::warn!("skipping box score entry: {}", e))
+                .ok()
+        })
+        .collect())
+}
+
+/// The box score encodes flags like `starter` as `"1"`/`"0"`
+fn deserialize_flag<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    Ok(match Value::deserialize(deserializer)? {
+        Value::Bool(b) => b,
+        Value::String(s) => s == "1" || s.eq_ignore_ascii_case("true"),
+        Value::Number(n) => n.as_u64() == Some(1),
+        _ => false,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Stats {
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub assists: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub blocks: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub blocks_received: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub field_goals_attempted: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub field_goals_made: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub field_goals_percentage: f32,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub fouls_drawn: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub fouls_offensive: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub fouls_personal: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub fouls_technical: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub free_throws_attempted: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub free_throws_made: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub free_throws_percentage: f32,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub minus: f32,
-    #[serde(default)]
-    pub minutes: String,
-    #[serde(default)]
-    pub minutes_calculated: String,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
+    pub minutes: GameClock,
+    #[serde(default, deserialize_with = "lenient")]
+    pub minutes_calculated: GameClock,
+    #[serde(default, deserialize_with = "lenient")]
     pub plus: f32,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub plus_minus_points: f32,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub points: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub points_fast_break: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub points_in_the_paint: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub points_second_chance: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub rebounds_defensive: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub rebounds_offensive: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub rebounds_total: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub steals: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub three_pointers_attempted: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub three_pointers_made: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub three_pointers_percentage: f32,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub turnovers: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub two_pointers_attempted: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub two_pointers_made: u8,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient")]
     pub two_pointers_percentage: f32,
 }
+
+/// The fewest minutes a player needs before per-36 rates are
+/// reported, a couple of minutes of garbage time would otherwise
+/// lead every category
+pub const PER_36_MIN_MINUTES: f32 = 10.0;
+
+impl Stats {
+    /// Sum the counting stats of `players`, the percentages
+    /// are recalculated from the totals
+    pub fn total<'a>(players: impl IntoIterator<Item = &'a Stats>) -> Stats {
+        let mut ret = Stats::default();
+        for p in players {
+            ret.assists = ret.assists.saturating_add(p.assists);
+            ret.blocks = ret.blocks.saturating_add(p.blocks);
+            ret.blocks_received = ret.blocks_received.saturating_add(p.blocks_received);
+            ret.field_goals_attempted = ret
+                .field_goals_attempted
+                .saturating_add(p.field_goals_attempted);
+            ret.field_goals_made = ret.field_goals_made.saturating_add(p.field_goals_made);
+            ret.fouls_drawn = ret.fouls_drawn.saturating_add(p.fouls_drawn);
+            ret.fouls_offensive = ret.fouls_offensive.saturating_add(p.fouls_offensive);
+            ret.fouls_personal = ret.fouls_personal.saturating_add(p.fouls_personal);
+            ret.fouls_technical = ret.fouls_technical.saturating_add(p.fouls_technical);
+            ret.free_throws_attempted = ret
+                .free_throws_attempted
+                .saturating_add(p.free_throws_attempted);
+            ret.free_throws_made = ret.free_throws_made.saturating_add(p.free_throws_made);
+            ret.minus += p.minus;
+            ret.plus += p.plus;
+            ret.plus_minus_points += p.plus_minus_points;
+            ret.points = ret.points.saturating_add(p.points);
+            ret.points_fast_break = ret.points_fast_break.saturating_add(p.points_fast_break);
+            ret.points_in_the_paint = ret
+                .points_in_the_paint
+                .saturating_add(p.points_in_the_paint);
+            ret.points_second_chance = ret
+                .points_second_chance
+                .saturating_add(p.points_second_chance);
+            ret.rebounds_defensive = ret.rebounds_defensive.saturating_add(p.rebounds_defensive);
+            ret.rebounds_offensive = ret.rebounds_offensive.saturating_add(p.rebounds_offensive);
+            ret.rebounds_total = ret.rebounds_total.saturating_add(p.rebounds_total);
+            ret.steals = ret.steals.saturating_add(p.steals);
+            ret.three_pointers_attempted = ret
+                .three_pointers_attempted
+                .saturating_add(p.three_pointers_attempted);
+            ret.three_pointers_made = ret
+                .three_pointers_made
+                .saturating_add(p.three_pointers_made);
+            ret.turnovers = ret.turnovers.saturating_add(p.turnovers);
+            ret.two_pointers_attempted = ret
+                .two_pointers_attempted
+                .saturating_add(p.two_pointers_attempted);
+            ret.two_pointers_made = ret.two_pointers_made.saturating_add(p.two_pointers_made);
+            ret.minutes = ret.minutes + p.minutes;
+            ret.minutes_calculated = ret.minutes_calculated + p.minutes_calculated;
+        }
+        let pct = |made: u8, attempted: u8| {
+            if attempted == 0 {
+                0.0
+            } else {
+                f32::from(made) / f32::from(attempted)
+            }
+        };
+        ret.field_goals_percentage = pct(ret.field_goals_made, ret.field_goals_attempted);
+        ret.free_throws_percentage = pct(ret.free_throws_made, ret.free_throws_attempted);
+        ret.three_pointers_percentage = pct(ret.three_pointers_made, ret.three_pointers_attempted);
+        ret.two_pointers_percentage = pct(ret.two_pointers_made, ret.two_pointers_attempted);
+        ret
+    }
+
+    /// Minutes played as decimal minutes
+    pub fn minutes_played(&self) -> f32 {
+        self.minutes.as_duration().as_secs_f32() / 60.0
+    }
+
+    /// `value` scaled to 36 minutes, `None` for players who haven't
+    /// played [`PER_36_MIN_MINUTES`] yet
+    pub fn per_36(&self, value: u8) -> Option<f32> {
+        let minutes = self.minutes_played();
+        if minutes < PER_36_MIN_MINUTES {
+            return None;
+        }
+        Some(f32::from(value) * 36.0 / minutes)
+    }
+
+    /// Points per shooting possession, `PTS / (2 * (FGA + 0.44 * FTA))`
+    pub fn true_shooting(&self) -> Option<f32> {
+        let attempts =
+            f32::from(self.field_goals_attempted) + 0.44 * f32::from(self.free_throws_attempted);
+        if attempts == 0.0 {
+            return None;
+        }
+        Some(f32::from(self.points) / (2.0 * attempts))
+    }
+
+    /// Field goal percentage with threes weighted by their extra point
+    pub fn effective_field_goal(&self) -> Option<f32> {
+        if self.field_goals_attempted == 0 {
+            return None;
+        }
+        let made = f32::from(self.field_goals_made) + 0.5 * f32::from(self.three_pointers_made);
+        Some(made / f32::from(self.field_goals_attempted))
+    }
+
+    /// John Hollinger's game score
+    pub fn game_score(&self) -> f32 {
+        f32::from(self.points) + 0.4 * f32::from(self.field_goals_made)
+            - 0.7 * f32::from(self.field_goals_attempted)
+            - 0.4 * (f32::from(self.free_throws_attempted) - f32::from(self.free_throws_made))
+            + 0.7 * f32::from(self.rebounds_offensive)
+            + 0.3 * f32::from(self.rebounds_defensive)
+            + f32::from(self.steals)
+            + 0.7 * f32::from(self.assists)
+            + 0.7 * f32::from(self.blocks)
+            - 0.4 * f32::from(self.fouls_personal)
+            - f32::from(self.turnovers)
+    }
+
+    /// The NBA efficiency rating, positive stats minus missed
+    /// shots and turnovers
+    pub fn efficiency(&self) -> f32 {
+        let positive = f32::from(self.points)
+            + f32::from(self.rebounds_total)
+            + f32::from(self.assists)
+            + f32::from(self.steals)
+            + f32::from(self.blocks);
+        let missed_fg = f32::from(self.field_goals_attempted) - f32::from(self.field_goals_made);
+        let missed_ft = f32::from(self.free_throws_attempted) - f32::from(self.free_throws_made);
+        positive - missed_fg - missed_ft - f32::from(self.turnovers)
+    }
+
+    fn possessions_used(&self) -> f32 {
+        f32::from(self.field_goals_attempted)
+            + 0.44 * f32::from(self.free_throws_attempted)
+            + f32::from(self.turnovers)
+    }
+
+    /// An estimate of the share of `team`'s possessions this player
+    /// used while on the floor
+    pub fn usage(&self, team: &Stats) -> Option<f32> {
+        let minutes = self.minutes_played();
+        let team_possessions = team.possessions_used();
+        if minutes == 0.0 || team_possessions == 0.0 {
+            return None;
+        }
+        Some(self.possessions_used() * (team.minutes_played() / 5.0) / (minutes * team_possessions))
+    }
+
+    /// Assists per turnover, a game without a turnover counts as
+    /// one so 10 assists and no turnovers is 10
+    pub fn assist_to_turnover(&self) -> f32 {
+        f32::from(self.assists) / f32::from(self.turnovers.max(1))
+    }
+
+    /// How many of points, rebounds, assists, steals and blocks
+    /// reached double digits
+    pub fn double_digit_categories(&self) -> u8 {
+        [
+            self.points,
+            self.rebounds_total,
+            self.assists,
+            self.steals,
+            self.blocks,
+        ]
+        .iter()
+        .filter(|v| **v >= 10)
+        .count() as u8
+    }
+
+    pub fn is_double_double(&self) -> bool {
+        self.double_digit_categories() >= 2
+    }
+
+    pub fn is_triple_double(&self) -> bool {
+        self.double_digit_categories() >= 3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_util::{box_scores, player, stat_player};
+
+    fn scorers(points: &[u8]) -> Vec<StatPlayer> {
+        points
+            .iter()
+            .enumerate()
+            .map(|(i, &points)| {
+                stat_player(
+                    i as u64 + 1,
+                    &format!("P. {}", i + 1),
+                    json!({ "points": points }),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn payload_numbers_as_strings_and_nulls() {
+        let json = json!({
+            "game": {
+                "gameId": "0022300001",
+                "gameStatus": "2",
+                "period": null,
+                "homeTeam": {
+                    "teamId": "1610612738",
+                    "teamTricode": "BOS",
+                    "score": "55",
+                    "players": [{
+                        "personId": "1628369",
+                        "nameI": "J. Tatum",
+                        "starter": "1",
+                        "statistics": {
+                            "points": "31",
+                            "assists": null,
+                            "reboundsTotal": "n/a",
+                            "steals": "",
+                            "fieldGoalsPercentage": "0.5",
+                            "minutes": "PT32M15.00S",
+                        },
+                    }],
+                    "statistics": {"points": 55, "benchPoints": "12"},
+                },
+                "awayTeam": {"teamTricode": "NYK"},
+            }
+        });
+        let game = GameBoxScores::from_json(&json.to_string()).unwrap();
+        assert_eq!(game.id, "0022300001");
+        let tatum = &game.home.players[0];
+        assert_eq!(tatum.person_id, 1628369);
+        assert!(tatum.starter);
+        let stats = &tatum.statistics;
+        assert_eq!(stats.points, 31);
+        assert_eq!(stats.assists, 0);
+        assert_eq!(stats.rebounds_total, 0);
+        assert_eq!(stats.steals, 0);
+        assert_eq!(stats.field_goals_percentage, 0.5);
+        assert_eq!(stats.minutes, GameClock::from_secs(32 * 60 + 15));
+        assert_eq!(game.home.statistics.totals.points, 55);
+        assert_eq!(game.home.statistics.bench_points, 12);
+        assert!(game.away.players.is_empty());
+    }
+
+    #[test]
+    fn payload_skips_bad_entries() {
+        let json = json!({
+            "game": {
+                "gameId": "0022300001",
+                "homeTeam": {
+                    "teamTricode": "BOS",
+                    "players": [
+                        {"personId": 1, "nameI": "J. Tatum"},
+                        {"personId": 2},
+                        "not a player",
+                    ],
+                },
+                "awayTeam": {"teamTricode": "NYK", "players": null},
+                "officials": [{"personId": "202041", "name": "Scott Foster"}, 7],
+            }
+        });
+        let game = GameBoxScores::from_json(&json.to_string()).unwrap();
+        let names: Vec<_> = game.home.players.iter().map(|p| &p.player_name).collect();
+        assert_eq!(names, vec!["J. Tatum"]);
+        assert!(game.away.players.is_empty());
+        assert_eq!(game.officials.len(), 1);
+        assert_eq!(game.officials[0].person_id, 202041);
+    }
+
+    #[test]
+    fn payload_needs_the_game_and_teams() {
+        assert!(GameBoxScores::from_json("{}").is_err());
+        assert!(GameBoxScores::from_json("not json").is_err());
+        let json = json!({"game": {"gameId": "1", "homeTeam": {"teamTricode": "BOS"}}});
+        let err = GameBoxScores::from_json(&json.to_string()).unwrap_err();
+        assert!(err.to_string().contains("awayTeam"), "{}", err);
+    }
+
+    #[test]
+    fn starter_flags_and_status() {
+        let parse = |fields: serde_json::Value| {
+            let mut player = json!({"personId": 1, "nameI": "J. Tatum"});
+            for (key, value) in fields.as_object().unwrap() {
+                player[key] = value.clone();
+            }
+            serde_json::from_value::<StatPlayer>(player).unwrap()
+        };
+        assert!(parse(json!({"starter": "1"})).starter);
+        assert!(parse(json!({"starter": true})).starter);
+        assert!(parse(json!({"starter": 1})).starter);
+        assert!(!parse(json!({"starter": "0"})).starter);
+        assert!(!parse(json!({})).starter);
+        assert_eq!(parse(json!({})).status, PlayerStatus::Active);
+        let out = parse(json!({
+            "status": "INACTIVE",
+            "notPlayingReason": "INACTIVE_INJURY",
+            "notPlayingDescription": "Left Ankle; Sprain",
+        }));
+        assert!(out.is_inactive());
+        assert_eq!(out.not_playing_reason.as_deref(), Some("INACTIVE_INJURY"));
+        assert_eq!(
+            parse(json!({"status": "SUSPENDED"})).status,
+            PlayerStatus::Other("SUSPENDED".to_string())
+        );
+    }
+
+    #[test]
+    fn starters_bench_and_inactive() {
+        let player = |id: u64, starter: bool, minutes: &str, points: u8| {
+            let mut p = stat_player(
+                id,
+                &format!("P. {}", id),
+                json!({"minutes": minutes, "points": points}),
+            );
+            p.starter = starter;
+            p
+        };
+        let mut players = vec![
+            player(1, true, "PT30M00.00S", 20),
+            player(2, true, "PT28M00.00S", 10),
+            player(3, false, "PT12M00.00S", 4),
+            player(4, false, "PT20M00.00S", 8),
+            player(5, false, "", 0),
+            player(6, false, "", 0),
+        ];
+        players[5].status = PlayerStatus::Inactive;
+        let team = TeamBoxScore {
+            abv: "BOS".to_string(),
+            box_score: BoxScore::from_players(&players),
+            players,
+            statistics: TeamStats::default(),
+        };
+        assert_eq!(team.points_split(), (30, 12));
+        let inactive: Vec<_> = team.inactive().map(|p| p.person_id).collect();
+        assert_eq!(inactive, vec![6]);
+        // the bench by minutes, leaving out who didn't play
+        let order: Vec<_> = team.table_order().iter().map(|p| p.person_id).collect();
+        assert_eq!(order, vec![1, 2, 4, 3]);
+    }
+
+    #[test]
+    fn minutes_parse_from_iso_durations() {
+        let stats = |minutes: &str| {
+            let stats = json!({"minutes": minutes, "minutesCalculated": "PT32M"});
+            stat_player(1, "J. Tatum", stats).statistics
+        };
+        let tatum = stats("PT32M15.00S");
+        assert_eq!(tatum.minutes_played(), 32.25);
+        assert_eq!(tatum.minutes_calculated, GameClock::from_secs(32 * 60));
+        assert_eq!(stats("").minutes_played(), 0.0);
+        assert_eq!(stats("32:15").minutes_played(), 0.0);
+        assert_eq!(StatCategory::Minutes.value(&tatum), 32.25);
+    }
+
+    #[test]
+    fn per_36_needs_enough_minutes() {
+        let stats = |minutes: &str| {
+            let stats = json!({"minutes": minutes, "points": 18, "reboundsTotal": 6});
+            stat_player(1, "J. Tatum", stats).statistics
+        };
+        assert_eq!(stats("PT24M00.00S").per_36(18), Some(27.0));
+        assert_eq!(
+            StatCategory::ReboundsPer36.value(&stats("PT24M00.00S")),
+            9.0
+        );
+        assert_eq!(stats("PT09M59.00S").per_36(18), None);
+        assert_eq!(StatCategory::PointsPer36.value(&stats("PT05M00.00S")), 0.0);
+    }
+
+    #[test]
+    fn find_player_by_any_name_or_id() {
+        let named = |id: u64, first: &str, family: &str| {
+            let mut p = player(id, &format!("{}. {}", &first[..1], family), true, json!({}));
+            p["firstName"] = json!(first);
+            p["familyName"] = json!(family);
+            p
+        };
+        let game = box_scores(
+            vec![
+                named(1628369, "Jayson", "Tatum"),
+                player(2, "J. Brown", true, json!({})),
+            ],
+            vec![named(1628973, "Jalen", "Brunson")],
+        );
+        let found = |query: &str| game.find_player(query).map(|p| p.person_id);
+        assert_eq!(found("Jayson Tatum"), Some(1628369));
+        assert_eq!(found("  tatum "), Some(1628369));
+        assert_eq!(found("J. Tatum"), Some(1628369));
+        assert_eq!(found("1628369"), Some(1628369));
+        assert_eq!(found("Brunson"), Some(1628973));
+        assert_eq!(found("J. Brown"), Some(2));
+        assert_eq!(found("Brown"), None);
+        assert_eq!(found("Jayson"), None);
+        assert_eq!(found(""), None);
+    }
+
+    #[test]
+    fn rank_groups_ties_highest_first() {
+        let ranks = StatCategory::Points.rank(&scorers(&[12, 31, 12, 0, 24]));
+        let names: Vec<_> = ranks.iter().map(|r| (r.names.clone(), r.value)).collect();
+        assert_eq!(
+            names,
+            vec![
+                (vec!["P. 2".to_string()], 31.0),
+                (vec!["P. 5".to_string()], 24.0),
+                (vec!["P. 1".to_string(), "P. 3".to_string()], 12.0),
+            ]
+        );
+        assert!(ranks[2].is_tie());
+    }
+
+    #[test]
+    fn signed_categories_keep_zeros_and_negatives() {
+        let players = vec![
+            stat_player(1, "A. One", json!({ "plusMinusPoints": -4.0 })),
+            stat_player(2, "B. Two", json!({ "plusMinusPoints": 0.0 })),
+        ];
+        let ranks = StatCategory::PlusMinus.rank(&players);
+        assert_eq!(ranks.len(), 2);
+        assert_eq!(ranks[1].value, -4.0);
+        assert!(StatCategory::Steals.rank(&players).is_empty());
+    }
+
+    #[test]
+    fn leaders_cover_n_players_and_ties() {
+        let box_score = BoxScore::from_players(&scorers(&[31, 24, 24, 18]));
+        assert_eq!(box_score.leader(StatCategory::Points).unwrap().value, 31.0);
+        // the tie for second is kept whole
+        let top: Vec<_> = box_score
+            .leaders(StatCategory::Points, 2)
+            .iter()
+            .map(|r| r.value)
+            .collect();
+        assert_eq!(top, vec![31.0, 24.0]);
+        assert_eq!(box_score.leaders(StatCategory::Points, 10).len(), 3);
+        assert!(box_score.leader(StatCategory::Blocks).is_none());
+    }
+
+    #[test]
+    fn category_names_parse() {
+        assert_eq!("Points".parse(), Ok(StatCategory::Points));
+        assert_eq!("fastbreakpoints".parse(), Ok(StatCategory::FastBreakPoints));
+        assert!("dunks"
+            .parse::<StatCategory>()
+            .unwrap_err()
+            .contains("points"));
+        for category in StatCategory::ALL.iter() {
+            assert_eq!(category.name().parse(), Ok(*category));
+        }
+    }
+
+    #[test]
+    fn rotation_entries_parse() {
+        let entry = |category, top| RotationEntry { category, top };
+        assert_eq!("pts:3".parse(), Ok(entry(StatCategory::Points, 3)));
+        assert_eq!("REB:1".parse(), Ok(entry(StatCategory::Rebounds, 1)));
+        assert_eq!("plusMinus".parse(), Ok(entry(StatCategory::PlusMinus, 1)));
+        assert!("pts:0".parse::<RotationEntry>().is_err());
+        assert!("pts:x".parse::<RotationEntry>().is_err());
+        assert!("dunks:2".parse::<RotationEntry>().is_err());
+    }
+
+    #[test]
+    fn values_that_display_the_same_tie() {
+        let shooter = |id: u64, pct: f32| {
+            let stats = json!({ "fieldGoalsAttempted": 10, "fieldGoalsPercentage": pct });
+            stat_player(id, &format!("P. {}", id), stats)
+        };
+        let players = vec![shooter(1, 0.5), shooter(2, 0.49996), shooter(3, 0.4)];
+        let ranks = StatCategory::FieldGoalsPercentage.rank(&players);
+        assert_eq!(ranks.len(), 2);
+        assert_eq!(ranks[0].names, vec!["P. 1", "P. 2"]);
+        assert!(!StatCategory::Points.is_tie(31.0, 30.0));
+    }
+
+    #[test]
+    fn assist_to_turnover_without_turnovers() {
+        let players = vec![
+            stat_player(1, "A. One", json!({ "assists": 10 })),
+            stat_player(2, "B. Two", json!({ "assists": 12, "turnovers": 3 })),
+        ];
+        assert_eq!(players[0].statistics.assist_to_turnover(), 10.0);
+        let leader = BoxScore::from_players(&players);
+        let leader = leader.leader(StatCategory::AssistToTurnover).unwrap();
+        assert_eq!(leader.names, vec!["A. One"]);
+    }
+
+    #[test]
+    fn percentages_need_enough_attempts() {
+        let players = vec![
+            stat_player(
+                1,
+                "A. One",
+                json!({"points": 2, "fieldGoalsMade": 1, "fieldGoalsAttempted": 1, "fieldGoalsPercentage": 1.0}),
+            ),
+            stat_player(
+                2,
+                "B. Two",
+                json!({"points": 13, "fieldGoalsMade": 6, "fieldGoalsAttempted": 10, "fieldGoalsPercentage": 0.6}),
+            ),
+        ];
+        let ranks = StatCategory::FieldGoalsPercentage.rank(&players);
+        assert_eq!(ranks.len(), 1);
+        assert_eq!(ranks[0].names, vec!["B. Two"]);
+        assert_eq!(StatCategory::TrueShooting.rank(&players).len(), 1);
+    }
+
+    #[test]
+    fn values_format_by_category() {
+        assert_eq!(StatCategory::Points.format_value(31.0), "31");
+        assert_eq!(
+            StatCategory::FieldGoalsPercentage.format_value(0.5),
+            "50.0%"
+        );
+        assert_eq!(StatCategory::Minutes.format_value(32.5), "32:30");
+        assert_eq!(StatCategory::PlusMinus.format_value(7.0), "+7");
+        assert_eq!(
+            StatCategory::DoubleDigits.format_value(3.0),
+            "triple-double"
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use std::{
+    convert::TryFrom,
+    fmt,
+    ops::{Add, Sub},
+    str::FromStr,
+    time::Duration,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The time remaining in a period, kept to the tenth of a second
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct GameClock {
+    tenths: u32,
+}
+
+impl GameClock {
+    pub fn from_tenths(tenths: u32) -> Self {
+        Self { tenths }
+    }
+
+    pub fn from_secs(secs: u32) -> Self {
+        Self {
+            tenths: secs.saturating_mul(10),
+        }
+    }
+
+    pub fn as_tenths(&self) -> u32 {
+        self.tenths
+    }
+
+    pub fn minutes(&self) -> u32 {
+        self.tenths / 600
+    }
+
+    pub fn seconds(&self) -> u32 {
+        self.tenths / 10 % 60
+    }
+
+    /// If the clock is inside the last minute of the period, where
+    /// the display switches to tenths of a second
+    pub fn is_last_minute(&self) -> bool {
+        self.tenths < 600
+    }
+
+    /// `self + rhs`, `None` if the total doesn't fit
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.tenths.checked_add(rhs.tenths).map(Self::from_tenths)
+    }
+
+    pub fn as_duration(&self) -> Duration {
+        Duration::from_millis(u64::from(self.tenths) * 100)
+    }
+}
+
+impl TryFrom<Duration> for GameClock {
+    type Error = DurationError;
+    fn try_from(d: Duration) -> Result<Self, Self::Error> {
+        u32::try_from(d.as_millis() / 100)
+            .map(Self::from_tenths)
+            .map_err(|_| DurationError::Overflow)
+    }
+}
+
+impl From<GameClock> for Duration {
+    fn from(clock: GameClock) -> Self {
+        clock.as_duration()
+    }
+}
+
+impl Sub for GameClock {
+    type Output = GameClock;
+    /// The time elapsed between two readings of the clock, since
+    /// the clock counts down this is `earlier - later`
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            tenths: self.tenths.saturating_sub(rhs.tenths),
+        }
+    }
+}
+
+impl Add for GameClock {
+    type Output = GameClock;
+    /// Saturates rather than overflowing, see
+    /// [`GameClock::checked_add`]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            tenths: self.tenths.saturating_add(rhs.tenths),
+        }
+    }
+}
+
+impl FromStr for GameClock {
+    type Err = DurationError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_iso_duration(s).and_then(Self::try_from)
+    }
+}
+
+impl fmt::Display for GameClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_last_minute() {
+            write!(f, "{}.{}", self.tenths / 10, self.tenths % 10)
+        } else {
+            write!(f, "{}:{:02}", self.minutes(), self.seconds())
+        }
+    }
+}
+
+impl Serialize for GameClock {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!(
+            "PT{:02}M{:02}.{}0S",
+            self.minutes(),
+            self.seconds(),
+            self.tenths % 10
+        ))
+    }
+}
+
+impl<'de> Deserialize<'de> for GameClock {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// The length of a regulation or overtime period
+pub fn period_length(period: u8) -> GameClock {
+    if period <= 4 {
+        GameClock::from_secs(12 * 60)
+    } else {
+        GameClock::from_secs(5 * 60)
+    }
+}
+
+/// The total game time elapsed at `clock` in `period`
+pub fn game_elapsed(period: u8, clock: GameClock) -> GameClock {
+    let before = (1..period.max(1))
+        .map(|p| period_length(p).as_tenths())
+        .sum::<u32>();
+    GameClock::from_tenths(before) + (period_length(period) - clock)
+}
+
+/// Deserialize a clock that may be `null` or an empty string, both
+/// of which the scoreboard uses when a game is not being played. A
+/// clock that doesn't parse is logged and left out rather than
+/// failing the whole scoreboard
+pub fn deserialize_optional<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<GameClock>, D::Error> {
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    match s.as_deref() {
+        None | Some("") => Ok(None),
+        Some(s) => Ok(s
+            .parse()
+            .map_err(|e| log::warn!("ignoring invalid game clock {:?}: {}", s, e))
+            .ok()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DurationError {
+    /// The input did not start with `PT`
+    MissingPrefix,
+    /// The input had no components after `PT`
+    Empty,
+    /// A character other than a digit, `.` or a unit
+    UnexpectedChar(char),
+    /// A unit appeared before a larger unit or more than once
+    OutOfOrder(char),
+    /// A number was missing, malformed or had a fraction before `S`
+    InvalidNumber(String),
+    /// The duration is too long to represent
+    Overflow,
+}
+
+impl fmt::Display for DurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingPrefix => write!(f, "duration must start with PT"),
+            Self::Empty => write!(f, "duration has no components"),
+            Self::UnexpectedChar(ch) => write!(f, "unexpected character {:?} in duration", ch),
+            Self::OutOfOrder(ch) => write!(f, "unit {:?} out of order in duration", ch),
+            Self::InvalidNumber(n) => write!(f, "invalid number {:?} in duration", n),
+            Self::Overflow => write!(f, "duration is too long"),
+        }
+    }
+}
+
+impl std::error::Error for DurationError {}
+
+/// Parse the ISO-8601 time durations the NBA feeds use for
+/// clocks and minutes played (e.g. `PT05M32.00S`, `PT32M15.00S`),
+/// only the `H`, `M` and `S` units are supported and only seconds
+/// may have a fraction
+pub fn parse_iso_duration(s: &str) -> Result<Duration, DurationError> {
+    let rest = s.strip_prefix("PT").ok_or(DurationError::MissingPrefix)?;
+    if rest.is_empty() {
+        return Err(DurationError::Empty);
+    }
+    let mut total = Duration::from_secs(0);
+    let mut last_unit = 0;
+    let mut num = String::new();
+    for ch in rest.chars() {
+        if ch.is_ascii_digit() || ch == '.' {
+            num.push(ch);
+            continue;
+        }
+        let (order, secs) = match ch {
+            'H' => (1, 3600),
+            'M' => (2, 60),
+            'S' => (3, 1),
+            _ => return Err(DurationError::UnexpectedChar(ch)),
+        };
+        if order <= last_unit {
+            return Err(DurationError::OutOfOrder(ch));
+        }
+        last_unit = order;
+        let (whole, nanos) = parse_component(&num, ch == 'S')?;
+        total = whole
+            .checked_mul(secs)
+            .map(Duration::from_secs)
+            .and_then(|d| d.checked_add(Duration::from_nanos(nanos)))
+            .and_then(|d| total.checked_add(d))
+            .ok_or(DurationError::Overflow)?;
+        num.clear();
+    }
+    if !num.is_empty() {
+        return Err(DurationError::InvalidNumber(num));
+    }
+    Ok(total)
+}
+
+fn parse_component(num: &str, allow_fraction: bool) -> Result<(u64, u64), DurationError> {
+    let invalid = || DurationError::InvalidNumber(num.to_string());
+    let (whole, fraction) = match num.split_once('.') {
+        Some((whole, fraction)) if allow_fraction => (whole, fraction),
+        Some(_) => return Err(invalid()),
+        None => (num, ""),
+    };
+    if whole.is_empty() || fraction.len() > 9 || fraction.contains('.') {
+        return Err(invalid());
+    }
+    let whole = whole.parse().map_err(|_| invalid())?;
+    let nanos = if fraction.is_empty() {
+        0
+    } else {
+        format!("{:0<9}", fraction).parse().map_err(|_| invalid())?
+    };
+    Ok((whole, nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_durations() {
+        assert_eq!(
+            parse_iso_duration("PT05M32.00S"),
+            Ok(Duration::from_secs(332))
+        );
+        assert_eq!(
+            parse_iso_duration("PT1H2M3.5S"),
+            Ok(Duration::from_millis(3_723_500))
+        );
+        assert_eq!(parse_iso_duration("PT12S"), Ok(Duration::from_secs(12)));
+    }
+
+    #[test]
+    fn reject_malformed_durations() {
+        assert_eq!(
+            parse_iso_duration("05M32S"),
+            Err(DurationError::MissingPrefix)
+        );
+        assert_eq!(parse_iso_duration("PT"), Err(DurationError::Empty));
+        assert_eq!(
+            parse_iso_duration("PT5X"),
+            Err(DurationError::UnexpectedChar('X'))
+        );
+        assert_eq!(
+            parse_iso_duration("PT5S3M"),
+            Err(DurationError::OutOfOrder('M'))
+        );
+        assert_eq!(
+            parse_iso_duration("PT1.5M"),
+            Err(DurationError::InvalidNumber("1.5".to_string()))
+        );
+        assert_eq!(
+            parse_iso_duration("PT5"),
+            Err(DurationError::InvalidNumber("5".to_string()))
+        );
+    }
+
+    #[test]
+    fn reject_huge_durations() {
+        assert_eq!(
+            parse_iso_duration("PT99999999999999999H"),
+            Err(DurationError::Overflow)
+        );
+        assert_eq!(
+            "PT9999999H".parse::<GameClock>(),
+            Err(DurationError::Overflow)
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            "PT05M32.00S".parse::<GameClock>().unwrap().to_string(),
+            "5:32"
+        );
+        assert_eq!(
+            "PT12M00.00S".parse::<GameClock>().unwrap().to_string(),
+            "12:00"
+        );
+        assert_eq!(
+            "PT00M12.40S".parse::<GameClock>().unwrap().to_string(),
+            "12.4"
+        );
+        assert_eq!(GameClock::from_secs(60).to_string(), "1:00");
+        assert_eq!(GameClock::from_tenths(599).to_string(), "59.9");
+    }
+
+    #[test]
+    fn serialize_round_trip() {
+        let clock = GameClock::from_tenths(3324);
+        let json = serde_json::to_string(&clock).unwrap();
+        assert_eq!(json, "\"PT05M32.40S\"");
+        assert_eq!(serde_json::from_str::<GameClock>(&json).unwrap(), clock);
+    }
+
+    #[test]
+    fn ordering_and_arithmetic() {
+        let early = GameClock::from_secs(600);
+        let late = GameClock::from_secs(332);
+        assert!(late < early);
+        assert_eq!(early - late, GameClock::from_secs(268));
+        assert_eq!(late - early, GameClock::default());
+        assert_eq!(early + late, GameClock::from_secs(932));
+        assert_eq!(
+            GameClock::from_tenths(u32::MAX) + late,
+            GameClock::from_tenths(u32::MAX)
+        );
+        assert_eq!(GameClock::from_tenths(u32::MAX).checked_add(late), None);
+        assert_eq!(
+            game_elapsed(2, GameClock::from_secs(600)),
+            GameClock::from_secs(14 * 60)
+        );
+        assert_eq!(
+            game_elapsed(5, GameClock::from_secs(0)),
+            GameClock::from_secs(53 * 60)
+        );
+    }
+}
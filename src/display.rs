@@ -0,0 +1,222 @@
+use std::convert::Infallible;
+
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+use crate::bitmap::{HEIGHT, WIDTH};
+
+/// The SSD1675 panel's own width, its rows are the screen's columns
+pub const PANEL_WIDTH: u32 = HEIGHT;
+pub const PANEL_HEIGHT: u32 = WIDTH;
+
+/// How the screen is turned relative to the panel, the same as the
+/// `rotation` the Adafruit driver is given
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    None,
+    Quarter,
+    Half,
+    ThreeQuarters,
+}
+
+/// The black/white RAM of an SSD1675 driven 250x122 panel, laid out
+/// so it can be sent to the controller as is: the panel's rows padded
+/// to a whole byte, most significant bit first, with a cleared bit for
+/// black. Drawing uses the screen's coordinates, which are turned
+/// onto the panel by the [`Rotation`], the Python renderer uses
+/// [`Rotation::ThreeQuarters`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ssd1675Buffer {
+    rotation: Rotation,
+    data: Vec<u8>,
+}
+
+impl Default for Ssd1675Buffer {
+    fn default() -> Self {
+        Self::new(Rotation::ThreeQuarters)
+    }
+}
+
+impl Ssd1675Buffer {
+    /// An all white buffer
+    pub fn new(rotation: Rotation) -> Self {
+        Self {
+            rotation,
+            data: vec![0xff; Self::stride() * PANEL_HEIGHT as usize],
+        }
+    }
+
+    /// The bytes in one of the panel's rows
+    pub fn stride() -> usize {
+        (PANEL_WIDTH as usize).div_ceil(8)
+    }
+
+    /// The contents of the controller's black/white RAM
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
+    }
+
+    /// If the pixel at `point` in screen coordinates is black
+    pub fn is_black(&self, point: Point) -> bool {
+        self.index(point)
+            .map(|(idx, bit)| self.data[idx] & bit == 0)
+            .unwrap_or(false)
+    }
+
+    /// The byte and bit for `point` in screen coordinates, `None`
+    /// when it is off the panel
+    fn index(&self, point: Point) -> Option<(usize, u8)> {
+        let size = self.size();
+        if point.x < 0
+            || point.y < 0
+            || point.x as u32 >= size.width
+            || point.y as u32 >= size.height
+        {
+            return None;
+        }
+        let (x, y) = (point.x as u32, point.y as u32);
+        let (panel_x, panel_y) = match self.rotation {
+            Rotation::None => (x, y),
+            Rotation::Quarter => (PANEL_WIDTH - 1 - y, x),
+            Rotation::Half => (PANEL_WIDTH - 1 - x, PANEL_HEIGHT - 1 - y),
+            Rotation::ThreeQuarters => (y, PANEL_HEIGHT - 1 - x),
+        };
+        let idx = panel_y as usize * Self::stride() + panel_x as usize / 8;
+        Some((idx, 0x80 >> (panel_x % 8)))
+    }
+}
+
+impl OriginDimensions for Ssd1675Buffer {
+    fn size(&self) -> Size {
+        match self.rotation {
+            Rotation::None | Rotation::Half => Size::new(PANEL_WIDTH, PANEL_HEIGHT),
+            Rotation::Quarter | Rotation::ThreeQuarters => Size::new(PANEL_HEIGHT, PANEL_WIDTH),
+        }
+    }
+}
+
+impl DrawTarget for Ssd1675Buffer {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let Some((idx, bit)) = self.index(point) {
+                if color.is_on() {
+                    self.data[idx] &= !bit;
+                } else {
+                    self.data[idx] |= bit;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let fill = if color.is_on() { 0x00 } else { 0xff };
+        self.data.iter_mut().for_each(|b| *b = fill);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bitmap::Renderer, Line};
+
+    /// The panel coordinates of the one black pixel in `buffer`
+    fn black_pixel(buffer: &Ssd1675Buffer) -> (u32, u32) {
+        let stride = Ssd1675Buffer::stride();
+        let (idx, byte) = buffer
+            .as_bytes()
+            .iter()
+            .enumerate()
+            .find(|(_, &b)| b != 0xff)
+            .expect("no black pixel");
+        let bit = byte.leading_ones();
+        ((idx % stride) as u32 * 8 + bit, (idx / stride) as u32)
+    }
+
+    #[test]
+    fn rotations_map_onto_the_panel() {
+        let cases = [
+            (Rotation::None, Point::new(3, 200), (3, 200)),
+            (
+                Rotation::Quarter,
+                Point::new(200, 3),
+                (PANEL_WIDTH - 1 - 3, 200),
+            ),
+            (
+                Rotation::Half,
+                Point::new(3, 200),
+                (PANEL_WIDTH - 1 - 3, PANEL_HEIGHT - 1 - 200),
+            ),
+            (Rotation::ThreeQuarters, Point::new(200, 3), (3, 249 - 200)),
+        ];
+        for &(rotation, point, panel) in cases.iter() {
+            let mut buffer = Ssd1675Buffer::new(rotation);
+            Pixel(point, BinaryColor::On).draw(&mut buffer).unwrap();
+            assert_eq!(black_pixel(&buffer), panel, "{:?}", rotation);
+            assert!(buffer.is_black(point));
+        }
+        let turned = Ssd1675Buffer::new(Rotation::ThreeQuarters);
+        assert_eq!(turned.size(), Size::new(WIDTH, HEIGHT));
+        let upright = Ssd1675Buffer::new(Rotation::None);
+        assert_eq!(upright.size(), Size::new(PANEL_WIDTH, PANEL_HEIGHT));
+    }
+
+    #[test]
+    fn rows_pad_to_a_whole_byte() {
+        assert_eq!(Ssd1675Buffer::stride(), 16);
+        let buffer = Ssd1675Buffer::default();
+        assert_eq!(buffer.as_bytes().len(), 16 * 250);
+        // the last pixel of a panel row is in its 16th byte
+        let mut buffer = Ssd1675Buffer::new(Rotation::None);
+        Pixel(Point::new(121, 0), BinaryColor::On)
+            .draw(&mut buffer)
+            .unwrap();
+        assert_eq!(buffer.as_bytes()[15], !0x40);
+    }
+
+    #[test]
+    fn a_cleared_bit_is_black() {
+        let mut buffer = Ssd1675Buffer::new(Rotation::None);
+        assert!(buffer.as_bytes().iter().all(|&b| b == 0xff));
+        Pixel(Point::new(0, 0), BinaryColor::On)
+            .draw(&mut buffer)
+            .unwrap();
+        assert_eq!(buffer.as_bytes()[0], 0x7f);
+        Pixel(Point::new(0, 0), BinaryColor::Off)
+            .draw(&mut buffer)
+            .unwrap();
+        assert_eq!(buffer.as_bytes()[0], 0xff);
+        buffer.clear(BinaryColor::On).unwrap();
+        assert!(buffer.as_bytes().iter().all(|&b| b == 0x00));
+        // off the panel is ignored
+        Pixel(Point::new(-1, 300), BinaryColor::Off)
+            .draw(&mut buffer)
+            .unwrap();
+        assert!(buffer.as_bytes().iter().all(|&b| b == 0x00));
+    }
+
+    #[test]
+    fn matches_the_bitmap() {
+        let renderer = Renderer::shared().unwrap();
+        let lines = [Line::small("Q4 5:32"), Line::large("BOS 98")];
+        let bitmap = renderer.render(&lines);
+        let mut buffer = Ssd1675Buffer::default();
+        renderer.draw(&lines, &mut buffer).unwrap();
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                let point = Point::new(x as i32, y as i32);
+                assert_eq!(buffer.is_black(point), bitmap.get(x, y), "{:?}", point);
+            }
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::box_score::{GameBoxScores, Stats};
+
+/// The fantasy points awarded for each stat
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FantasyScoring {
+    pub points: f32,
+    pub rebounds: f32,
+    pub assists: f32,
+    pub steals: f32,
+    pub blocks: f32,
+    /// Usually negative
+    pub turnovers: f32,
+}
+
+impl Default for FantasyScoring {
+    fn default() -> Self {
+        Self {
+            points: 1.0,
+            rebounds: 1.2,
+            assists: 1.5,
+            steals: 3.0,
+            blocks: 3.0,
+            turnovers: -1.0,
+        }
+    }
+}
+
+impl FantasyScoring {
+    pub fn score(&self, stats: &Stats) -> f32 {
+        self.points * f32::from(stats.points)
+            + self.rebounds * f32::from(stats.rebounds_total)
+            + self.assists * f32::from(stats.assists)
+            + self.steals * f32::from(stats.steals)
+            + self.blocks * f32::from(stats.blocks)
+            + self.turnovers * f32::from(stats.turnovers)
+    }
+}
+
+/// Parse weights like `pts=1,reb=1.2,tov=-1`, any stat left
+/// out keeps its default weight
+impl FromStr for FantasyScoring {
+    type Err = FantasyScoringError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ret = Self::default();
+        for pair in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (stat, weight) = pair
+                .split_once('=')
+                .ok_or_else(|| FantasyScoringError::MissingWeight(pair.to_string()))?;
+            let weight: f32 = weight
+                .trim()
+                .parse()
+                .map_err(|_| FantasyScoringError::InvalidWeight(weight.trim().to_string()))?;
+            let field = match stat.trim().to_ascii_lowercase().as_str() {
+                "pts" | "points" => &mut ret.points,
+                "reb" | "rebounds" => &mut ret.rebounds,
+                "ast" | "assists" => &mut ret.assists,
+                "stl" | "steals" => &mut ret.steals,
+                "blk" | "blocks" => &mut ret.blocks,
+                "tov" | "to" | "turnovers" => &mut ret.turnovers,
+                _ => return Err(FantasyScoringError::UnknownStat(stat.trim().to_string())),
+            };
+            *field = weight;
+        }
+        Ok(ret)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FantasyScoringError {
+    /// A stat other than pts, reb, ast, stl, blk or tov
+    UnknownStat(String),
+    /// A stat without an `=weight`
+    MissingWeight(String),
+    /// A weight that isn't a number
+    InvalidWeight(String),
+}
+
+impl fmt::Display for FantasyScoringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownStat(stat) => write!(
+                f,
+                "unknown fantasy stat {:?}, expected pts, reb, ast, stl, blk or tov",
+                stat
+            ),
+            Self::MissingWeight(pair) => write!(f, "expected stat=weight, found {:?}", pair),
+            Self::InvalidWeight(weight) => write!(f, "invalid fantasy weight {:?}", weight),
+        }
+    }
+}
+
+impl std::error::Error for FantasyScoringError {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FantasyPlayer {
+    pub name: String,
+    pub team: String,
+    pub score: f32,
+}
+
+/// The running score for a fantasy roster across every game
+/// with a box score
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FantasyTotal {
+    /// Highest score first
+    pub players: Vec<FantasyPlayer>,
+    pub total: f32,
+}
+
+impl FantasyTotal {
+    /// Score every player in `roster` found in `box_scores`, players
+    /// who aren't playing tonight are left out
+    pub fn from_box_scores(
+        roster: &[String],
+        box_scores: &[GameBoxScores],
+        scoring: &FantasyScoring,
+    ) -> Self {
+        let mut players: Vec<_> = roster
+            .iter()
+            .filter_map(|query| {
+                box_scores.iter().find_map(|game| {
+                    let player = game.find_player(query)?;
+                    let team = if game.home.players.contains(player) {
+                        &game.home.abv
+                    } else {
+                        &game.away.abv
+                    };
+                    Some(FantasyPlayer {
+                        name: player.player_name.clone(),
+                        team: team.clone(),
+                        score: scoring.score(&player.statistics),
+                    })
+                })
+            })
+            .collect();
+        players.sort_by(|lhs, rhs| {
+            rhs.score
+                .partial_cmp(&lhs.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let total = players.iter().map(|p| p.score).sum();
+        Self { players, total }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_util::{box_scores, player, stat_player, AWAY, HOME};
+
+    fn line(points: u8, rebounds: u8, assists: u8) -> serde_json::Value {
+        json!({
+            "points": points,
+            "reboundsTotal": rebounds,
+            "assists": assists,
+            "steals": 1,
+            "blocks": 1,
+            "turnovers": 2,
+        })
+    }
+
+    #[test]
+    fn default_and_custom_scoring() {
+        assert_eq!("".parse(), Ok(FantasyScoring::default()));
+        let scoring: FantasyScoring = "pts=2, REB=1,tov=-2.5".parse().unwrap();
+        assert_eq!(
+            scoring,
+            FantasyScoring {
+                points: 2.0,
+                rebounds: 1.0,
+                turnovers: -2.5,
+                ..FantasyScoring::default()
+            }
+        );
+        let stats = stat_player(1, "J. Tatum", line(30, 10, 5)).statistics;
+        // 30 + 12 + 7.5 + 3 + 3 - 2
+        assert_eq!(FantasyScoring::default().score(&stats), 53.5);
+        // 60 + 10 + 7.5 + 3 + 3 - 5
+        assert_eq!(scoring.score(&stats), 78.5);
+    }
+
+    #[test]
+    fn malformed_scoring() {
+        assert_eq!(
+            "pts".parse::<FantasyScoring>(),
+            Err(FantasyScoringError::MissingWeight("pts".to_string()))
+        );
+        assert_eq!(
+            "pts=lots".parse::<FantasyScoring>(),
+            Err(FantasyScoringError::InvalidWeight("lots".to_string()))
+        );
+        assert_eq!(
+            "fouls=-1".parse::<FantasyScoring>(),
+            Err(FantasyScoringError::UnknownStat("fouls".to_string()))
+        );
+    }
+
+    #[test]
+    fn total_across_games() {
+        let tonight = [
+            box_scores(
+                vec![player(1, "J. Tatum", true, line(30, 10, 5))],
+                vec![player(11, "J. Brunson", true, line(20, 2, 8))],
+            ),
+            box_scores(
+                vec![player(21, "N. Jokic", true, line(25, 12, 10))],
+                Vec::new(),
+            ),
+        ];
+        let roster = [
+            "j. brunson".to_string(),
+            "21".to_string(),
+            "J. Tatum".to_string(),
+            "V. Wembanyama".to_string(),
+        ];
+        let total = FantasyTotal::from_box_scores(&roster, &tonight, &FantasyScoring::default());
+        let scores: Vec<_> = total
+            .players
+            .iter()
+            .map(|p| (p.name.as_str(), p.team.as_str(), format!("{:.1}", p.score)))
+            .collect();
+        // Wembanyama isn't in any box score so is left out
+        assert_eq!(
+            scores,
+            vec![
+                ("N. Jokic", HOME, "58.4".to_string()),
+                ("J. Tatum", HOME, "53.5".to_string()),
+                ("J. Brunson", AWAY, "38.4".to_string()),
+            ]
+        );
+        assert_eq!(format!("{:.1}", total.total), "150.3");
+    }
+}
@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+
+use crate::action::{Action, ActionKind};
+
+/// The action kinds shown on the live action line when
+/// none are configured, substitutions, timeouts and other
+/// stoppages are skipped since they say little about the game
+pub const DEFAULT_KINDS: [ActionKind; 10] = [
+    ActionKind::Period,
+    ActionKind::JumpBall,
+    ActionKind::Points,
+    ActionKind::Rebound,
+    ActionKind::Block,
+    ActionKind::Turnover,
+    ActionKind::Steal,
+    ActionKind::Foul,
+    ActionKind::FreeThrow,
+    ActionKind::Violation,
+];
+
+/// Selects which play from the play-by-play is worth showing
+/// on the live action line
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayFilter {
+    /// The action kinds to keep, empty keeps every kind
+    pub kinds: Vec<ActionKind>,
+    /// The team tri-codes to keep, empty keeps every team
+    pub teams: Vec<String>,
+    /// Only keep made shots and free throws
+    pub scoring_only: bool,
+    /// Only keep actions credited to the team being followed
+    pub our_team_only: bool,
+}
+
+impl Default for PlayFilter {
+    fn default() -> Self {
+        Self {
+            kinds: DEFAULT_KINDS.to_vec(),
+            teams: Vec::new(),
+            scoring_only: false,
+            our_team_only: false,
+        }
+    }
+}
+
+impl PlayFilter {
+    /// A filter that keeps every action
+    pub fn all() -> Self {
+        Self {
+            kinds: Vec::new(),
+            ..Self::default()
+        }
+    }
+
+    pub fn matches(&self, action: &Action, our_team: &str) -> bool {
+        if !self.kinds.is_empty() && !self.kinds.contains(&action.kind()) {
+            return false;
+        }
+        if self.scoring_only && !action.is_scoring() {
+            return false;
+        }
+        let team = action.team();
+        if self.our_team_only
+            && !team
+                .map(|t| t.eq_ignore_ascii_case(our_team))
+                .unwrap_or(false)
+        {
+            return false;
+        }
+        if !self.teams.is_empty()
+            && !team
+                .map(|t| self.teams.iter().any(|f| f.eq_ignore_ascii_case(t)))
+                .unwrap_or(false)
+        {
+            return false;
+        }
+        true
+    }
+
+    /// The most recent play that passes this filter, `plays`
+    /// is expected to be sorted by action number
+    pub fn pick<'a>(&self, plays: &'a [Action], our_team: &str) -> Option<&'a Action> {
+        plays.iter().rev().find(|play| self.matches(play, our_team))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{action, AWAY, HOME};
+    use serde_json::json;
+
+    fn plays() -> Vec<Action> {
+        vec![
+            action(json!({
+                "actionNumber": 1,
+                "actionType": "2pt",
+                "subType": "Jump Shot",
+                "shotResult": "Made",
+                "scoreHome": "2",
+            })),
+            action(json!({
+                "actionNumber": 2,
+                "actionType": "rebound",
+                "subType": "defensive",
+                "teamTricode": AWAY,
+            })),
+            action(json!({
+                "actionNumber": 3,
+                "actionType": "timeout",
+                "teamTricode": AWAY,
+            })),
+            action(json!({
+                "actionNumber": 4,
+                "actionType": "substitution",
+                "subType": "in",
+            })),
+        ]
+    }
+
+    #[test]
+    fn default_skips_stoppages() {
+        let plays = plays();
+        let picked = PlayFilter::default().pick(&plays, HOME).unwrap();
+        assert_eq!(picked.number(), 2);
+    }
+
+    #[test]
+    fn all_keeps_everything() {
+        let plays = plays();
+        assert_eq!(PlayFilter::all().pick(&plays, HOME).unwrap().number(), 4);
+    }
+
+    #[test]
+    fn scoring_and_team() {
+        let plays = plays();
+        let scoring = PlayFilter {
+            scoring_only: true,
+            ..PlayFilter::default()
+        };
+        assert_eq!(scoring.pick(&plays, AWAY).unwrap().number(), 1);
+        let ours = PlayFilter {
+            our_team_only: true,
+            ..PlayFilter::default()
+        };
+        assert_eq!(ours.pick(&plays, HOME).unwrap().number(), 1);
+        assert_eq!(ours.pick(&plays, "nyk").unwrap().number(), 2);
+        let teams = PlayFilter {
+            teams: vec!["LAL".to_string()],
+            ..PlayFilter::default()
+        };
+        assert!(teams.pick(&plays, HOME).is_none());
+    }
+}
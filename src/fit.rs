@@ -0,0 +1,438 @@
+use crate::{
+    bitmap::{Renderer, HEIGHT, LARGE_PX, MEDIUM_PX, SMALL_PX, WIDTH},
+    Line,
+};
+
+/// One way of making a line fit the display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FitStep {
+    /// Shorten player names and common play words, teams are
+    /// already tri codes
+    Abbreviate,
+    /// Drop to the next smaller size, down to small
+    Shrink,
+    /// Split onto a second line at the same size
+    Wrap,
+    /// Cut the end off and add `…`
+    Ellipsize,
+}
+
+/// The steps to try, in order, until a line fits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FitPolicy {
+    pub steps: &'static [FitStep],
+}
+
+impl FitPolicy {
+    /// Leave the line as is, the renderer will clip it
+    pub const CLIP: Self = Self { steps: &[] };
+    /// For the one line under the score, it can get smaller but
+    /// there is no room for a second line
+    pub const SINGLE_LINE: Self = Self {
+        steps: &[FitStep::Abbreviate, FitStep::Shrink, FitStep::Ellipsize],
+    };
+    /// For screens with room to spare
+    pub const WRAP: Self = Self {
+        steps: &[
+            FitStep::Abbreviate,
+            FitStep::Shrink,
+            FitStep::Wrap,
+            FitStep::Ellipsize,
+        ],
+    };
+    /// For lines that are already small
+    pub const SMALL: Self = Self {
+        steps: &[FitStep::Abbreviate, FitStep::Ellipsize],
+    };
+}
+
+/// Words shortened by [`FitStep::Abbreviate`] whatever their case,
+/// longest first so a phrase is replaced before the words in it
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("Free Throw", "FT"),
+    ("Jump Shot", "Jumper"),
+    ("Personal", "Pers."),
+    ("Offensive", "Off."),
+    ("Defensive", "Def."),
+    ("Technical", "Tech."),
+    ("Turnover", "TO"),
+    ("Shooting", "Shoot."),
+    ("Rebound", "Reb"),
+    ("Assists", "Ast"),
+    ("Minutes", "Min"),
+];
+
+fn px(line: &Line) -> f32 {
+    match line {
+        Line::Small(_) => SMALL_PX,
+        Line::Medium(_) => MEDIUM_PX,
+        Line::Large(_) => LARGE_PX,
+    }
+}
+
+/// A line the same size as `line` with `text`
+fn same_size(line: &Line, text: String) -> Line {
+    match line {
+        Line::Small(_) => Line::Small(text),
+        Line::Medium(_) => Line::Medium(text),
+        Line::Large(_) => Line::Large(text),
+    }
+}
+
+fn fits(renderer: &Renderer, line: &Line) -> bool {
+    renderer.text_width(line.text(), px(line)) <= WIDTH as i32
+}
+
+/// Apply the steps of `policy` to `line` until every line fits the
+/// display's width, more than one line when it wraps. Rows of cells
+/// are laid out by the renderer and left alone
+pub fn fit(line: Line, policy: FitPolicy) -> Vec<Line> {
+    fit_steps(line, policy.steps)
+}
+
+fn fit_steps(line: Line, steps: &[FitStep]) -> Vec<Line> {
+    let renderer = match Renderer::shared() {
+        Some(renderer) if !line.has_cells() => renderer,
+        _ => return vec![line],
+    };
+    let mut lines = vec![line];
+    for step in steps {
+        if lines.iter().all(|l| fits(renderer, l)) {
+            break;
+        }
+        lines = lines
+            .into_iter()
+            .flat_map(|line| {
+                if fits(renderer, &line) {
+                    return vec![line];
+                }
+                match step {
+                    FitStep::Abbreviate => vec![same_size(&line, abbreviate(line.text()))],
+                    FitStep::Shrink => vec![shrink(line)],
+                    FitStep::Wrap => wrap(renderer, line),
+                    FitStep::Ellipsize => vec![ellipsize(renderer, line)],
+                }
+            })
+            .collect();
+    }
+    lines
+}
+
+/// [`fit`] and render the result
+pub fn render(line: Line, policy: FitPolicy) -> String {
+    fit(line, policy).iter().map(Line::render).collect()
+}
+
+/// Build a screen that fits the display: the lines of `head` as they
+/// are, then `body`, then as many of `extras` as there is height for.
+/// Showing an extra comes before wrapping a line, so lines are only
+/// wrapped when there is room left for it. Body lines that still run
+/// past the bottom are dropped
+pub fn compose(head: &str, body: Vec<(Line, FitPolicy)>, extras: Vec<(Line, FitPolicy)>) -> String {
+    let head: Vec<_> = head.lines().filter_map(Line::parse).collect();
+    let renderer = if let Some(renderer) = Renderer::shared() {
+        renderer
+    } else {
+        return head
+            .iter()
+            .chain(body.iter().map(|(line, _)| line))
+            .chain(extras.iter().map(|(line, _)| line))
+            .map(Line::render)
+            .collect();
+    };
+    let fitted = |lines: &[(Line, FitPolicy)], wrap: bool| -> Vec<Line> {
+        lines
+            .iter()
+            .flat_map(|(line, policy)| {
+                let steps: Vec<_> = policy
+                    .steps
+                    .iter()
+                    .copied()
+                    .filter(|&step| wrap || step != FitStep::Wrap)
+                    .collect();
+                fit_steps(line.clone(), &steps)
+            })
+            .collect()
+    };
+    let fits_height = |lines: &[Line]| renderer.layout(lines, WIDTH as i32).bottom <= HEIGHT as i32;
+    for shown in (0..=extras.len()).rev() {
+        for &wrap in &[true, false] {
+            let mut lines = head.clone();
+            lines.extend(fitted(&body, wrap));
+            lines.extend(fitted(&extras[..shown], wrap));
+            if fits_height(&lines) {
+                return lines.iter().map(Line::render).collect();
+            }
+        }
+    }
+    let mut lines = head;
+    for line in fitted(&body, false) {
+        lines.push(line);
+        if !fits_height(&lines) {
+            log::debug!("dropping lines past the bottom of the screen");
+            lines.pop();
+            break;
+        }
+    }
+    lines.iter().map(Line::render).collect()
+}
+
+fn abbreviate(text: &str) -> String {
+    let mut ret = text.to_string();
+    for (long, short) in ABBREVIATIONS {
+        ret = replace_word(&ret, long, short);
+    }
+    // `J. Tatum` to `Tatum` and `(J. Brown` to `(Brown`
+    let words: Vec<_> = ret.split(' ').collect();
+    let mut kept: Vec<String> = Vec::with_capacity(words.len());
+    let mut prefix = "";
+    for (i, word) in words.iter().enumerate() {
+        let (open, name) = match word.strip_prefix('(') {
+            Some(name) => ("(", name),
+            None => ("", *word),
+        };
+        let is_initial = name.len() == 2
+            && name.ends_with('.')
+            && name.starts_with(|c: char| c.is_ascii_uppercase())
+            && words
+                .get(i + 1)
+                .map(|next| next.starts_with(|c: char| c.is_uppercase()))
+                .unwrap_or(false);
+        if is_initial {
+            prefix = open;
+        } else {
+            kept.push(format!("{}{}", prefix, word));
+            prefix = "";
+        }
+    }
+    kept.join(" ")
+}
+
+/// Replace `long` where it isn't part of a longer word, so
+/// `Nets` doesn't match inside `Hornets`. Case is ignored and a
+/// lowercase match gets a lowercase `short`, `jump shot` to `jumper`
+fn replace_word(text: &str, long: &str, short: &str) -> String {
+    let is_word = |c: Option<char>| c.map(char::is_alphanumeric).unwrap_or(false);
+    // ASCII lowercasing keeps the byte offsets the same as `text`
+    let lower = text.to_ascii_lowercase();
+    let long_lower = long.to_ascii_lowercase();
+    let mut ret = String::with_capacity(text.len());
+    let mut start = 0;
+    while let Some(found) = lower[start..].find(&long_lower) {
+        let idx = start + found;
+        let end = idx + long.len();
+        let before = text[..idx].chars().last();
+        let after = text[end..].chars().next();
+        ret.push_str(&text[start..idx]);
+        let matched = &text[idx..end];
+        if is_word(before) || is_word(after) {
+            ret.push_str(matched);
+        } else if matched.starts_with(char::is_lowercase) && short.contains(char::is_lowercase) {
+            ret.push_str(&short.to_lowercase());
+        } else {
+            ret.push_str(short);
+        }
+        start = end;
+    }
+    ret.push_str(&text[start..]);
+    ret
+}
+
+fn shrink(line: Line) -> Line {
+    match line {
+        Line::Large(text) => Line::Medium(text),
+        Line::Medium(text) | Line::Small(text) => Line::Small(text),
+    }
+}
+
+/// Break before the last word that fits, the second line keeps the
+/// rest even if it is still too long
+fn wrap(renderer: &Renderer, line: Line) -> Vec<Line> {
+    let words: Vec<_> = line.text().split(' ').collect();
+    let split = (1..words.len())
+        .rev()
+        .find(|&i| fits(renderer, &same_size(&line, words[..i].join(" "))));
+    match split {
+        Some(i) => vec![
+            same_size(&line, words[..i].join(" ")),
+            same_size(&line, words[i..].join(" ")),
+        ],
+        None => vec![line],
+    }
+}
+
+fn ellipsize(renderer: &Renderer, line: Line) -> Line {
+    let mut chars: Vec<_> = line.text().chars().collect();
+    while !chars.is_empty() {
+        chars.pop();
+        let shortened: String =
+            chars.iter().collect::<String>().trim_end().to_string() + "\u{2026}";
+        let candidate = same_size(&line, shortened);
+        if fits(renderer, &candidate) {
+            return candidate;
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_util::{action, AWAY};
+
+    const LONG_ACTION: &str = "Q4 6:10 Made 3pts BOS J. Tatum 26' step back jump shot (28 PTS)";
+
+    fn head() -> String {
+        [
+            Line::small("\u{2022}\u{2022}\tQ4 6:10\t\u{2022}"),
+            Line::large("BOS NYK"),
+            Line::large(" 98  97"),
+        ]
+        .iter()
+        .map(Line::render)
+        .collect()
+    }
+
+    fn parse(screen: &str) -> Vec<Line> {
+        screen.lines().filter_map(Line::parse).collect()
+    }
+
+    fn bottom(lines: &[Line]) -> i32 {
+        Renderer::shared()
+            .unwrap()
+            .layout(lines, WIDTH as i32)
+            .bottom
+    }
+
+    #[test]
+    fn wraps_when_there_is_room() {
+        let screen = compose(
+            &head(),
+            vec![(Line::medium(LONG_ACTION), FitPolicy::WRAP)],
+            Vec::new(),
+        );
+        let lines = parse(&screen);
+        assert_eq!(lines.len(), 5);
+        assert!(bottom(&lines) <= HEIGHT as i32);
+    }
+
+    #[test]
+    fn extras_come_before_wrapping() {
+        let extras = vec![
+            (Line::small("BOS 10-0 run"), FitPolicy::SMALL),
+            (Line::small("Fouls BOS 3 NYK 5"), FitPolicy::SMALL),
+        ];
+        let screen = compose(
+            &head(),
+            vec![(Line::medium(LONG_ACTION), FitPolicy::WRAP)],
+            extras,
+        );
+        let lines = parse(&screen);
+        assert_eq!(lines.len(), 6);
+        assert!(lines[3].text().ends_with('\u{2026}'));
+        assert_eq!(lines[5], Line::small("Fouls BOS 3 NYK 5"));
+        assert!(bottom(&lines) <= HEIGHT as i32);
+    }
+
+    #[test]
+    fn extras_are_dropped_from_the_bottom() {
+        let extras = (0..5)
+            .map(|i| (Line::small(format!("extra {}", i)), FitPolicy::SMALL))
+            .collect();
+        let screen = compose(
+            &head(),
+            vec![(Line::medium("Q4 6:10 Timeout BOS"), FitPolicy::WRAP)],
+            extras,
+        );
+        let lines = parse(&screen);
+        assert!(bottom(&lines) <= HEIGHT as i32);
+        assert_eq!(lines[3], Line::medium("Q4 6:10 Timeout BOS"));
+        assert_eq!(lines[4], Line::small("extra 0"));
+        assert!(lines.len() < 9);
+    }
+
+    #[test]
+    fn body_past_the_bottom_is_dropped() {
+        let body = (0..8)
+            .map(|i| (Line::medium(format!("line {}", i)), FitPolicy::SMALL))
+            .collect();
+        let lines = parse(&compose(&head(), body, Vec::new()));
+        assert!(bottom(&lines) <= HEIGHT as i32);
+        assert_eq!(lines[3], Line::medium("line 0"));
+    }
+
+    #[test]
+    fn abbreviate_names_and_words() {
+        let free_throw = action(json!({
+            "actionType": "freethrow",
+            "subType": "1 of 2",
+            "shotResult": "Made",
+        }));
+        let jumper = action(json!({
+            "actionType": "2pt",
+            "subType": "Jump Shot",
+            "shotResult": "Missed",
+            "playerNameI": "J. Brown",
+        }));
+        let rebound = action(json!({
+            "actionType": "rebound",
+            "subType": "offensive",
+            "teamTricode": AWAY,
+            "playerNameI": "M. Robinson",
+        }));
+        let descs: Vec<_> = [free_throw, jumper, rebound]
+            .iter()
+            .map(|play| abbreviate(play.desc()))
+            .collect();
+        assert_eq!(
+            descs,
+            vec![
+                "FT Tatum 1 of 2 Made",
+                "Missed 2pts BOS Brown jumper",
+                "off. reb Robinson (NYK)",
+            ]
+        );
+        // an initial needs a name after it
+        assert_eq!(abbreviate("Plan B. then"), "Plan B. then");
+    }
+
+    #[test]
+    fn replace_whole_words_only() {
+        assert_eq!(
+            replace_word("Hornets beat the Nets", "Nets", "BKN"),
+            "Hornets beat the BKN"
+        );
+        assert_eq!(replace_word("Nets-Hornets", "Nets", "BKN"), "BKN-Hornets");
+        assert_eq!(replace_word("Reboundss", "Rebound", "Reb"), "Reboundss");
+        assert_eq!(
+            replace_word("TURNOVER, turnover", "Turnover", "TO"),
+            "TO, TO"
+        );
+    }
+
+    #[test]
+    fn wrap_at_the_last_word_that_fits() {
+        let renderer = Renderer::shared().unwrap();
+        let lines = wrap(renderer, Line::medium(LONG_ACTION));
+        assert_eq!(lines.len(), 2);
+        assert!(fits(renderer, &lines[0]));
+        assert_eq!(
+            format!("{} {}", lines[0].text(), lines[1].text()),
+            LONG_ACTION
+        );
+        // a single word too long for the line can't be split
+        let word = Line::medium("W".repeat(40));
+        assert_eq!(wrap(renderer, word.clone()), vec![word]);
+    }
+
+    #[test]
+    fn ellipsize_fits() {
+        let renderer = Renderer::shared().unwrap();
+        let line = ellipsize(renderer, Line::small(LONG_ACTION.repeat(2)));
+        assert!(fits(renderer, &line));
+        assert!(line.text().ends_with('\u{2026}'));
+        assert!(LONG_ACTION.starts_with(line.text().trim_end_matches('\u{2026}')));
+    }
+}
@@ -0,0 +1,330 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    action::{Action, FoulKind},
+    clock::GameClock,
+};
+
+/// The number of personal fouls that disqualifies a player
+pub const FOUL_OUT: u8 = 6;
+/// The number of personal fouls that puts a player in foul trouble
+pub const FOUL_TROUBLE: u8 = 5;
+/// How long, in seconds of game time, a foul trouble alert stays
+/// on the live screen
+pub const ALERT_WINDOW_SECS: u32 = 60;
+
+/// The team fouls in a period after which the other team shoots
+/// free throws on every foul
+pub fn bonus_limit(period: u8) -> u8 {
+    if period <= 4 {
+        4
+    } else {
+        3
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerFouls {
+    pub team: String,
+    pub person_id: u64,
+    pub name: String,
+    pub personal: u8,
+    pub technical: u8,
+    /// The action number of this player's most recent foul
+    pub last_foul: i64,
+    pub last_foul_period: u8,
+    /// The game time elapsed at this player's most recent foul
+    pub last_foul_elapsed: GameClock,
+}
+
+impl PlayerFouls {
+    pub fn fouled_out(&self) -> bool {
+        self.personal >= FOUL_OUT
+    }
+
+    pub fn in_foul_trouble(&self) -> bool {
+        self.personal >= FOUL_TROUBLE
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamPeriodFouls {
+    pub team: String,
+    pub period: u8,
+    pub fouls: u8,
+    /// Fouls committed inside the last two minutes of the period
+    pub last_two_minutes: u8,
+}
+
+impl TeamPeriodFouls {
+    /// If this team's fouls have put the other team in the bonus
+    pub fn gives_bonus(&self) -> bool {
+        self.fouls >= bonus_limit(self.period) || self.last_two_minutes >= 1
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum FoulAlert {
+    Trouble {
+        team: String,
+        name: String,
+        fouls: u8,
+    },
+    FouledOut {
+        team: String,
+        name: String,
+    },
+}
+
+impl std::fmt::Display for FoulAlert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Trouble { team, name, fouls } => write!(f, "{} ({}) {} fouls", name, team, fouls),
+            Self::FouledOut { team, name } => write!(f, "{} ({}) fouled out", name, team),
+        }
+    }
+}
+
+/// Personal foul tallies per player and team fouls per period
+/// built from the foul actions in the play-by-play
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FoulTracker {
+    pub players: Vec<PlayerFouls>,
+    pub teams: Vec<TeamPeriodFouls>,
+    pub current_period: u8,
+    /// The game time elapsed at the latest action
+    pub elapsed: GameClock,
+}
+
+impl FoulTracker {
+    /// Tally the fouls in `plays`, which are expected to be sorted
+    /// by action number
+    pub fn from_plays(plays: &[Action]) -> Self {
+        let mut ret = Self::default();
+        for play in plays {
+            ret.apply(play);
+        }
+        ret
+    }
+
+    pub fn apply(&mut self, action: &Action) {
+        self.current_period = self.current_period.max(action.quarter());
+        self.elapsed = self.elapsed.max(action.elapsed());
+        let kind = if let Some(kind) = action.foul_kind() {
+            kind
+        } else {
+            return;
+        };
+        let team = if let Some(team) = action.team() {
+            team
+        } else {
+            return;
+        };
+        let technical = matches!(kind, FoulKind::Technical);
+        if let (Some(person_id), Some(name)) = (action.person_id(), action.player()) {
+            let player = self.player_entry(team, person_id, name);
+            if technical {
+                player.technical += 1;
+            } else {
+                player.personal += 1;
+                if let Some(total) = action.foul_personal_total() {
+                    player.personal = player.personal.max(total);
+                }
+            }
+            player.last_foul = action.number();
+            player.last_foul_period = action.quarter();
+            player.last_foul_elapsed = action.elapsed();
+        }
+        // offensive fouls and technicals don't count toward the bonus
+        if technical || matches!(kind, FoulKind::Offensive) {
+            return;
+        }
+        let last_two = action.clock() <= GameClock::from_secs(2 * 60);
+        let period = action.quarter();
+        let team_fouls = self.team_entry(team, period);
+        team_fouls.fouls += 1;
+        if last_two {
+            team_fouls.last_two_minutes += 1;
+        }
+    }
+
+    fn player_entry(&mut self, team: &str, person_id: u64, name: &str) -> &mut PlayerFouls {
+        if let Some(idx) = self.players.iter().position(|p| p.person_id == person_id) {
+            return &mut self.players[idx];
+        }
+        self.players.push(PlayerFouls {
+            team: team.to_string(),
+            person_id,
+            name: name.to_string(),
+            personal: 0,
+            technical: 0,
+            last_foul: 0,
+            last_foul_period: 0,
+            last_foul_elapsed: GameClock::default(),
+        });
+        self.players.last_mut().unwrap()
+    }
+
+    fn team_entry(&mut self, team: &str, period: u8) -> &mut TeamPeriodFouls {
+        if let Some(idx) = self
+            .teams
+            .iter()
+            .position(|t| t.period == period && t.team == team)
+        {
+            return &mut self.teams[idx];
+        }
+        self.teams.push(TeamPeriodFouls {
+            team: team.to_string(),
+            period,
+            fouls: 0,
+            last_two_minutes: 0,
+        });
+        self.teams.last_mut().unwrap()
+    }
+
+    pub fn player(&self, person_id: u64) -> Option<&PlayerFouls> {
+        self.players.iter().find(|p| p.person_id == person_id)
+    }
+
+    /// The team fouls `team` has committed in the current period
+    pub fn team_fouls(&self, team: &str) -> u8 {
+        self.team_period(team).map(|t| t.fouls).unwrap_or(0)
+    }
+
+    fn team_period(&self, team: &str) -> Option<&TeamPeriodFouls> {
+        self.teams
+            .iter()
+            .find(|t| t.period == self.current_period && t.team.eq_ignore_ascii_case(team))
+    }
+
+    /// If `team` has committed enough fouls in the current period to
+    /// put its opponent in the bonus
+    pub fn gives_bonus(&self, team: &str) -> bool {
+        self.team_period(team)
+            .map(TeamPeriodFouls::gives_bonus)
+            .unwrap_or(false)
+    }
+
+    /// Every player in foul trouble or fouled out, most recent first
+    pub fn alerts(&self) -> Vec<FoulAlert> {
+        alerts(self.players.iter())
+    }
+
+    /// Alerts for fouls committed in the last
+    /// [`ALERT_WINDOW_SECS`] of the current period, the ones worth
+    /// showing on the live screen
+    pub fn recent_alerts(&self) -> Vec<FoulAlert> {
+        let window = GameClock::from_secs(ALERT_WINDOW_SECS);
+        alerts(self.players.iter().filter(|p| {
+            p.last_foul_period == self.current_period
+                && self.elapsed - p.last_foul_elapsed <= window
+        }))
+    }
+}
+
+fn alerts<'a>(players: impl Iterator<Item = &'a PlayerFouls>) -> Vec<FoulAlert> {
+    let mut players: Vec<_> = players.filter(|p| p.in_foul_trouble()).collect();
+    players.sort_by_key(|p| std::cmp::Reverse(p.last_foul));
+    players
+        .into_iter()
+        .map(|p| {
+            if p.fouled_out() {
+                FoulAlert::FouledOut {
+                    team: p.team.clone(),
+                    name: p.name.clone(),
+                }
+            } else {
+                FoulAlert::Trouble {
+                    team: p.team.clone(),
+                    name: p.name.clone(),
+                    fouls: p.personal,
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{action, AWAY, HOME};
+    use serde_json::json;
+
+    fn foul(number: i64, period: u8, clock: &str, person_id: u64, kind: &str) -> Action {
+        action(json!({
+            "actionNumber": number,
+            "actionType": "foul",
+            "subType": kind,
+            "period": period,
+            "clock": clock,
+            "personId": person_id,
+        }))
+    }
+
+    #[test]
+    fn foul_out_at_six() {
+        let plays: Vec<_> = (1..=6)
+            .map(|n| foul(n, 1, "PT10M00.00S", 7, "personal"))
+            .collect();
+        let tracker = FoulTracker::from_plays(&plays[..5]);
+        assert!(tracker.player(7).unwrap().in_foul_trouble());
+        assert!(!tracker.player(7).unwrap().fouled_out());
+        let tracker = FoulTracker::from_plays(&plays);
+        assert!(tracker.player(7).unwrap().fouled_out());
+        assert!(matches!(
+            tracker.alerts().first(),
+            Some(FoulAlert::FouledOut { .. })
+        ));
+    }
+
+    #[test]
+    fn technicals_are_not_personals() {
+        let plays = vec![
+            foul(1, 1, "PT10M00.00S", 7, "personal"),
+            foul(2, 1, "PT09M00.00S", 7, "technical"),
+        ];
+        let tracker = FoulTracker::from_plays(&plays);
+        let player = tracker.player(7).unwrap();
+        assert_eq!(player.personal, 1);
+        assert_eq!(player.technical, 1);
+        assert_eq!(tracker.team_fouls(HOME), 1);
+    }
+
+    #[test]
+    fn team_fouls_reset_each_period() {
+        let mut plays: Vec<_> = (1..=4)
+            .map(|n| foul(n, 1, "PT10M00.00S", n as u64, "personal"))
+            .collect();
+        let tracker = FoulTracker::from_plays(&plays);
+        assert_eq!(tracker.team_fouls(HOME), 4);
+        assert!(tracker.gives_bonus(HOME));
+        assert!(!tracker.gives_bonus(AWAY));
+        plays.push(foul(5, 2, "PT11M00.00S", 1, "personal"));
+        let tracker = FoulTracker::from_plays(&plays);
+        assert_eq!(tracker.team_fouls(HOME), 1);
+        assert!(!tracker.gives_bonus(HOME));
+        assert_eq!(tracker.player(1).unwrap().personal, 2);
+    }
+
+    #[test]
+    fn last_two_minutes_bonus() {
+        let plays = vec![foul(1, 4, "PT01M30.00S", 7, "personal")];
+        assert!(FoulTracker::from_plays(&plays).gives_bonus(HOME));
+    }
+
+    #[test]
+    fn alerts_expire() {
+        let mut plays: Vec<_> = (1..=5)
+            .map(|n| foul(n, 1, "PT10M00.00S", 7, "personal"))
+            .collect();
+        assert_eq!(FoulTracker::from_plays(&plays).recent_alerts().len(), 1);
+        plays.push(foul(6, 1, "PT08M00.00S", 8, "personal"));
+        let tracker = FoulTracker::from_plays(&plays);
+        assert!(tracker.recent_alerts().is_empty());
+        assert_eq!(tracker.alerts().len(), 1);
+    }
+}
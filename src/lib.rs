@@ -1,14 +1,33 @@
 use action::Action;
 use chrono::{DateTime, Datelike, Local, Utc};
+use clock::GameClock;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 pub mod action;
+pub mod analysis;
+pub mod bitmap;
 pub mod box_score;
+pub mod clock;
+pub mod display;
+pub mod fantasy;
+pub mod filter;
+pub mod fit;
+pub mod fouls;
+pub mod lineup;
+pub mod milestones;
+pub mod plus_minus;
 pub mod render;
+#[cfg(test)]
+mod test_util;
 
 const TODAY_URL: &str =
     "https://cdn.nba.com/static/json/liveData/scoreboard/todaysScoreboard_00.json";
 
+/// Separates the cells of a row, a line on its own with cells is
+/// spread across the screen, see [`bitmap::Renderer::layout`]
+pub const CELL_SEPARATOR: char = '\t';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Line {
     Small(String),
     Medium(String),
@@ -26,6 +45,31 @@ impl Line {
         Self::Large(s.to_string())
     }
 
+    /// Parse one line of a data file, the reverse of
+    /// [`Line::render`]. A line with only the size is a blank line and
+    /// an unknown size is treated as small like the Python renderer
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut chars = line.chars();
+        let size = chars.next()?;
+        let text = chars.as_str().to_string();
+        Some(match size {
+            '1' => Self::Medium(text),
+            '2' => Self::Large(text),
+            _ => Self::Small(text),
+        })
+    }
+
+    pub fn text(&self) -> &str {
+        match self {
+            Line::Small(text) | Line::Medium(text) | Line::Large(text) => text,
+        }
+    }
+
+    /// If this line is a row of cells rather than one run of text
+    pub fn has_cells(&self) -> bool {
+        self.text().contains(CELL_SEPARATOR)
+    }
+
     pub fn render(&self) -> String {
         match self {
             Line::Small(line) => format!("{}{}\n", 0, line),
@@ -48,10 +92,10 @@ pub async fn find_last_game(team_avb: &str) -> Option<Game> {
             })
             .ok()?;
         for game in day.games.into_iter() {
-            if game.home.tri_code == team_avb || game.away.tri_code == team_avb {
-                if game.end_time.is_some() {
-                    return Some(game);
-                }
+            if (game.home.tri_code == team_avb || game.away.tri_code == team_avb)
+                && game.end_time.is_some()
+            {
+                return Some(game);
             }
         }
     }
@@ -69,13 +113,8 @@ pub async fn find_game_today(team_abv: &str) -> Option<Game> {
                 e
             })
             .ok()?;
-        for mut game in day.scoreboard.games.into_iter() {
+        for game in day.scoreboard.games.into_iter() {
             if game.home.tri_code == team_abv || game.away.tri_code == team_abv {
-                if let Some(new_clock) = action::duration_to_clock(&game.clock) {
-                    game.clock = new_clock;
-                } else {
-                    log::warn!("Failed to parse clock: {:?}", game.clock);
-                }
                 return Some(game);
             }
         }
@@ -84,6 +123,19 @@ pub async fn find_game_today(team_abv: &str) -> Option<Game> {
     None
 }
 
+/// Every game on today's scoreboard
+pub async fn find_games_today() -> Option<Vec<Game>> {
+    let json = request_with_retry(TODAY_URL).await?;
+    let day: Today = serde_json::from_str(&json)
+        .map_err(|e| {
+            log::error!("failed to parse today, writing debug output: {}", e);
+            std::fs::write("today_err.json", &json).unwrap();
+            e
+        })
+        .ok()?;
+    Some(day.scoreboard.games)
+}
+
 pub async fn find_next_game(team_avb: &str) -> Option<Game> {
     let today = Local::now();
     for i in 1..6 {
@@ -91,17 +143,16 @@ pub async fn find_next_game(team_avb: &str) -> Option<Game> {
         let json = request_with_retry(&url).await?;
         std::fs::write("today.json", &json).unwrap();
         let day: Day = serde_json::from_str(&json)
-            .map_err(|e| {
+            .inspect_err(|_| {
                 log::error!("failed to deserailize next day");
                 std::fs::write("next_day.json", &json).unwrap();
-                e
             })
             .ok()?;
         for game in day.games.into_iter() {
-            if game.home.tri_code == team_avb || game.away.tri_code == team_avb {
-                if game.end_time.is_none() {
-                    return Some(game);
-                }
+            if (game.home.tri_code == team_avb || game.away.tri_code == team_avb)
+                && game.end_time.is_none()
+            {
+                return Some(game);
             }
         }
     }
@@ -115,14 +166,15 @@ pub async fn get_game_boxscore(game_id: &str) -> Option<box_score::GameBoxScores
         game_id
     );
     let s = request_with_retry(&url).await?;
-    let bs: serde_json::Map<String, Value> = serde_json::from_str(&s)
+    box_score::GameBoxScores::from_json(&s)
         .map_err(|e| {
             log::error!("failed to parse box score json: {}", e);
-            std::fs::write("box_score_err.json", s).unwrap();
+            if let Err(e) = std::fs::write("box_score_err.json", &s) {
+                log::error!("failed to write box score debug output: {}", e);
+            }
             e
         })
-        .ok()?;
-    box_score::GameBoxScores::try_from_obj(&bs)
+        .ok()
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -160,7 +212,7 @@ pub async fn get_play_by_play(
             action::Action::try_from_obj(m.as_object().unwrap().to_owned(), home_team, away_team)
         })
         .collect();
-    ret.sort_by(|lhs, rhs| lhs.number().cmp(&rhs.number()));
+    ret.sort_by_key(|action| action.number());
     Some(ret)
 }
 
@@ -180,8 +232,8 @@ pub struct Game {
     pub start_time: chrono::DateTime<Utc>,
     #[serde(alias = "endTimeUTC")]
     end_time: Option<DateTime<Utc>>,
-    #[serde(alias = "gameClock")]
-    pub clock: String,
+    #[serde(alias = "gameClock", deserialize_with = "clock::deserialize_optional")]
+    pub clock: Option<GameClock>,
     pub period: PeriodOrNumber,
     #[serde(alias = "hTeam")]
     #[serde(alias = "homeTeam")]
@@ -190,16 +242,55 @@ pub struct Game {
     #[serde(alias = "awayTeam")]
     pub away: Team,
     pub game_leaders: Option<GameLeaders>,
+    /// The id of the team with the ball, the scoreboard doesn't
+    /// include this so it is filled in from the play-by-play
+    #[serde(default)]
+    pub possession: Option<StringOrNumber>,
+    /// The scoreboard's own summary, e.g. "Q3 5:32", "Half" or
+    /// "End of Q3"
+    #[serde(default)]
+    pub game_status_text: Option<String>,
 }
 
 impl Game {
+    pub fn has_possession(&self, team: &Team) -> bool {
+        self.possession
+            .as_ref()
+            .map(|p| p.to_string() == team.id.to_string())
+            .unwrap_or(false)
+    }
+
     pub fn has_ended(&self) -> bool {
-        self.end_time.is_some() || self.clock == ""
+        self.end_time.is_some() || self.clock.is_none()
+    }
+
+    pub fn has_started(&self) -> bool {
+        self.start_time < Utc::now()
     }
 
     pub fn is_active(&self) -> bool {
         !self.has_ended() && self.start_time < Utc::now()
     }
+
+    /// If play is stopped between periods, including halftime. The
+    /// live scoreboard only sends the period number, so this falls
+    /// back to the clock having run out with a status of "Half" or
+    /// "End of ..", or the last play ending the period
+    pub fn is_break(&self, plays: &[Action]) -> bool {
+        if self.period.is_break() {
+            return true;
+        }
+        let clock_out = self.clock.map(|c| c.as_tenths() == 0).unwrap_or(false);
+        let status_break = self
+            .game_status_text
+            .as_deref()
+            .map(|s| s.trim() == "Half" || s.trim().starts_with("End of"))
+            .unwrap_or(false);
+        if clock_out && status_break {
+            return true;
+        }
+        plays.last().and_then(Action::period_event) == Some(&action::PeriodEvent::End)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -216,6 +307,14 @@ impl PeriodOrNumber {
             Self::Number(inner) => *inner,
         }
     }
+
+    /// If play is stopped between periods, including halftime
+    pub fn is_break(&self) -> bool {
+        match self {
+            Self::Period(inner) => inner.is_halftime || inner.is_end_of_period,
+            Self::Number(_) => false,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -255,6 +354,12 @@ pub enum StringOrNumber {
     Number(u32),
 }
 
+impl Team {
+    pub fn is_in_bonus(&self) -> bool {
+        self.in_bonus.as_ref().map(|b| *b == "1").unwrap_or(false)
+    }
+}
+
 impl std::fmt::Display for StringOrNumber {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -276,7 +381,9 @@ impl PartialEq<&str> for StringOrNumber {
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct LineScore {
-    score: StringOrNumber,
+    #[serde(default)]
+    pub period: Option<u8>,
+    pub score: StringOrNumber,
 }
 
 fn url_for_date(dt: impl Datelike) -> String {
@@ -321,13 +428,6 @@ pub struct GameLeaders {
     pub home_leaders: GameLeader,
     pub away_leaders: GameLeader,
 }
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(untagged)]
-enum Leaders {
-    List(Vec<GameLeader>),
-    Single(GameLeader),
-}
-
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct GameLeader {
@@ -348,3 +448,63 @@ pub struct GameLeader {
     #[serde(default)]
     pub assists: u8,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        test_util::{action, game, game_json, made_shot, HOME},
+        GameClock, Today,
+    };
+
+    #[test]
+    fn playing_is_not_a_break() {
+        let plays = [made_shot(2, "PT05M32.00S", HOME, 2, 0)];
+        assert!(!game(json!({})).is_break(&plays));
+    }
+
+    #[test]
+    fn break_from_the_status_text() {
+        for status in &["Half", "End of Q1", "End of 1st OT"] {
+            let game = game(json!({"gameClock": "PT00M00.00S", "gameStatusText": status}));
+            assert!(game.is_break(&[]), "{}", status);
+        }
+        // the clock has to have run out too
+        let game = game(json!({"gameStatusText": "Half"}));
+        assert!(!game.is_break(&[]));
+    }
+
+    #[test]
+    fn break_from_the_last_play() {
+        let game = game(json!({"gameClock": "PT00M00.00S", "gameStatusText": "Q2 0:00"}));
+        let end = action(json!({"actionNumber": 3, "subType": "end", "clock": "PT00M00.00S"}));
+        assert!(game.is_break(&[end]));
+        let start = action(json!({"actionNumber": 4, "period": 3}));
+        assert!(!game.is_break(&[start]));
+    }
+
+    #[test]
+    fn a_bad_clock_keeps_the_scoreboard() {
+        let game = |id: &str, clock: &str| game_json(json!({"gameId": id, "gameClock": clock}));
+        let json = json!({
+            "scoreboard": {
+                "gameDate": "2023-10-24",
+                "leagueId": "00",
+                "games": [game("1", "PT05M32.00S"), game("2", "5:32"), game("3", "")],
+            }
+        });
+        let today: Today = serde_json::from_value(json).unwrap();
+        let clocks: Vec<_> = today.scoreboard.games.iter().map(|g| g.clock).collect();
+        assert_eq!(
+            clocks,
+            vec![Some(GameClock::from_secs(5 * 60 + 32)), None, None]
+        );
+    }
+
+    #[test]
+    fn break_from_the_older_period_object() {
+        let period = json!({"current": 2, "type": 0, "isHalftime": true, "isEndOfPeriod": false});
+        assert!(game(json!({ "period": period })).is_break(&[]));
+    }
+}
@@ -0,0 +1,313 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    action::{Action, PeriodEvent, SubstitutionKind},
+    box_score::{GameBoxScores, TeamBoxScore},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct OnCourt {
+    pub person_id: u64,
+    pub name: String,
+}
+
+/// The players one team has on the floor
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Lineup {
+    pub team: String,
+    pub players: Vec<OnCourt>,
+}
+
+impl Lineup {
+    pub fn from_starters(team: &TeamBoxScore) -> Self {
+        Self {
+            team: team.abv.clone(),
+            players: team
+                .starters()
+                .map(|p| OnCourt {
+                    person_id: p.person_id,
+                    name: p.player_name.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// The players the box score has on the floor, `None` unless
+    /// it has exactly five
+    pub fn from_on_court(team: &TeamBoxScore) -> Option<Self> {
+        let players: Vec<_> = team
+            .players
+            .iter()
+            .filter(|p| p.on_court)
+            .map(|p| OnCourt {
+                person_id: p.person_id,
+                name: p.player_name.clone(),
+            })
+            .collect();
+        if players.len() != 5 {
+            return None;
+        }
+        Some(Self {
+            team: team.abv.clone(),
+            players,
+        })
+    }
+
+    /// If there are exactly five players on the floor
+    pub fn is_complete(&self) -> bool {
+        self.players.len() == 5
+    }
+
+    fn check(&self, when: &str) {
+        if !self.is_complete() {
+            log::warn!(
+                "{} has {} players on court {}",
+                self.team,
+                self.players.len(),
+                when
+            );
+        }
+    }
+
+    /// The five who started `period`, worked out from `plays`.
+    /// Substitutions between periods aren't always logged, so a
+    /// player who shows up in the period before being subbed in
+    /// started it. Anyone still missing is taken from `self`, the
+    /// lineup that ended the last period
+    fn period_starters(&self, plays: &[Action], period: u8) -> Self {
+        let mut players: Vec<OnCourt> = Vec::new();
+        let mut subbed_in = Vec::new();
+        let period_plays = plays
+            .iter()
+            .filter(|p| p.quarter() == period && p.team() == Some(self.team.as_str()));
+        for play in period_plays {
+            let person_id = match play.person_id() {
+                Some(id) => id,
+                None => continue,
+            };
+            if play.substitution_kind() == Some(&SubstitutionKind::In) {
+                subbed_in.push(person_id);
+            } else if !subbed_in.contains(&person_id)
+                && !players.iter().any(|p| p.person_id == person_id)
+            {
+                players.push(OnCourt {
+                    person_id,
+                    name: play.player().unwrap_or_default().to_string(),
+                });
+            }
+            if players.len() == 5 {
+                break;
+            }
+        }
+        for player in &self.players {
+            if players.len() >= 5 {
+                break;
+            }
+            if !subbed_in.contains(&player.person_id)
+                && !players.iter().any(|p| p.person_id == player.person_id)
+            {
+                players.push(player.clone());
+            }
+        }
+        Self {
+            team: self.team.clone(),
+            players,
+        }
+    }
+
+    pub fn contains(&self, person_id: u64) -> bool {
+        self.players.iter().any(|p| p.person_id == person_id)
+    }
+
+    /// The person ids on the floor in ascending order, two lineups
+    /// with the same players have the same key
+    pub fn key(&self) -> Vec<u64> {
+        let mut ids: Vec<_> = self.players.iter().map(|p| p.person_id).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    fn substitute(&mut self, kind: &SubstitutionKind, person_id: u64, name: &str) {
+        match kind {
+            SubstitutionKind::Out => self.players.retain(|p| p.person_id != person_id),
+            SubstitutionKind::In => {
+                if !self.contains(person_id) {
+                    self.players.push(OnCourt {
+                        person_id,
+                        name: name.to_string(),
+                    })
+                }
+            }
+            SubstitutionKind::Other(other) => {
+                log::warn!("Unknown substitution type {:?}", other);
+            }
+        }
+    }
+}
+
+/// The five players on court for each team, kept up to date
+/// by applying substitutions in order and resetting at the
+/// start of each period
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LineupTracker {
+    pub home: Lineup,
+    pub away: Lineup,
+}
+
+impl LineupTracker {
+    pub fn from_starters(box_scores: &GameBoxScores) -> Self {
+        Self {
+            home: Lineup::from_starters(&box_scores.home),
+            away: Lineup::from_starters(&box_scores.away),
+        }
+    }
+
+    /// Start from the starters and replay `plays`, which are
+    /// expected to be sorted by action number. The box score's
+    /// `oncourt` flags have the final say on who is on the floor now
+    pub fn from_plays(box_scores: &GameBoxScores, plays: &[Action]) -> Self {
+        let mut ret = Self::from_starters(box_scores);
+        for idx in 0..plays.len() {
+            ret.step(plays, idx);
+        }
+        for (lineup, team) in [
+            (&mut ret.home, &box_scores.home),
+            (&mut ret.away, &box_scores.away),
+        ] {
+            match Lineup::from_on_court(team) {
+                Some(on_court) if on_court.key() != lineup.key() => {
+                    log::debug!("{} lineup drifted from the box score", lineup.team);
+                    *lineup = on_court;
+                }
+                Some(_) => {}
+                None => lineup.check("after the last play"),
+            }
+        }
+        ret
+    }
+
+    /// Update the lineups for `plays[idx]`, the start of a period
+    /// after the first resets both lineups, see
+    /// [`Lineup::period_starters`]. Returns `true` if a lineup changed
+    pub fn step(&mut self, plays: &[Action], idx: usize) -> bool {
+        let play = &plays[idx];
+        if play.period_event() == Some(&PeriodEvent::Start) && play.quarter() > 1 {
+            let period = play.quarter();
+            self.home = self.home.period_starters(plays, period);
+            self.away = self.away.period_starters(plays, period);
+            let when = format!("at the start of Q{}", period);
+            self.home.check(&when);
+            self.away.check(&when);
+            return true;
+        }
+        self.apply(play)
+    }
+
+    /// Update the lineups if `action` is a substitution, returns
+    /// `true` if a lineup changed
+    pub fn apply(&mut self, action: &Action) -> bool {
+        let kind = if let Some(kind) = action.substitution_kind() {
+            kind
+        } else {
+            return false;
+        };
+        let (person_id, team) = match (action.person_id(), action.team()) {
+            (Some(id), Some(team)) => (id, team),
+            _ => {
+                log::warn!("Substitution without a player or team: {}", action.desc());
+                return false;
+            }
+        };
+        let name = action.player().unwrap_or_default();
+        let lineup = if team == self.home.team {
+            &mut self.home
+        } else if team == self.away.team {
+            &mut self.away
+        } else {
+            log::warn!("Substitution for unknown team {}", team);
+            return false;
+        };
+        lineup.substitute(kind, person_id, name);
+        true
+    }
+
+    pub fn team(&self, team: &str) -> Option<&Lineup> {
+        if self.home.team.eq_ignore_ascii_case(team) {
+            Some(&self.home)
+        } else if self.away.team.eq_ignore_ascii_case(team) {
+            Some(&self.away)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{action, box_scores, roster, substitution, AWAY, HOME};
+    use serde_json::json;
+
+    fn sub(number: i64, period: u8, person_id: u64, kind: &str) -> Action {
+        substitution(number, period, "PT12M00.00S", person_id, kind)
+    }
+
+    fn rebound(number: i64, period: u8, person_id: u64) -> Action {
+        action(json!({
+            "actionNumber": number,
+            "actionType": "rebound",
+            "subType": "defensive",
+            "period": period,
+            "personId": person_id,
+        }))
+    }
+
+    fn period_start(number: i64, period: u8) -> Action {
+        action(json!({"actionNumber": number, "period": period}))
+    }
+
+    #[test]
+    fn substitutions() {
+        let box_scores = box_scores(roster(1, 7), roster(11, 5));
+        let plays = vec![sub(1, 1, 1, "out"), sub(2, 1, 6, "in")];
+        let tracker = LineupTracker::from_plays(&box_scores, &plays);
+        assert_eq!(tracker.home.key(), vec![2, 3, 4, 5, 6]);
+        assert_eq!(tracker.away.key(), vec![11, 12, 13, 14, 15]);
+        assert_eq!(tracker.team("nyk"), Some(&tracker.away));
+        assert_eq!(tracker.team(AWAY).unwrap().team, AWAY);
+    }
+
+    #[test]
+    fn period_start_resets_lineup() {
+        let box_scores = box_scores(roster(1, 7), roster(11, 5));
+        // 1 and 7 come back for Q2 without a logged substitution
+        let plays = vec![
+            sub(1, 1, 1, "out"),
+            sub(2, 1, 6, "in"),
+            period_start(3, 2),
+            rebound(4, 2, 1),
+            rebound(5, 2, 7),
+            sub(6, 2, 2, "out"),
+            sub(7, 2, 6, "in"),
+        ];
+        let tracker = LineupTracker::from_plays(&box_scores, &plays);
+        assert_eq!(tracker.home.team, HOME);
+        assert_eq!(tracker.home.key(), vec![1, 3, 4, 6, 7]);
+        assert!(tracker.home.is_complete());
+    }
+
+    #[test]
+    fn box_score_on_court_wins() {
+        let mut home = roster(1, 7);
+        for player in &mut home {
+            let id = player["personId"].as_u64().unwrap();
+            player["oncourt"] = json!(if id > 2 { "1" } else { "0" });
+        }
+        let box_scores = box_scores(home, roster(11, 5));
+        let tracker = LineupTracker::from_plays(&box_scores, &[]);
+        assert_eq!(tracker.home.key(), vec![3, 4, 5, 6, 7]);
+    }
+}
@@ -1,10 +1,28 @@
-use nba_eink_disp::*;
+use nba_eink_disp::{
+    action::ActionKind,
+    analysis::{Momentum, MomentumConfig},
+    box_score::{GameBoxScores, RotationEntry, StatCategory},
+    clock::GameClock,
+    display::Ssd1675Buffer,
+    fantasy::{FantasyScoring, FantasyTotal},
+    filter::PlayFilter,
+    fit::FitPolicy,
+    fouls::FoulTracker,
+    lineup::LineupTracker,
+    milestones::{self, Archive, Milestone, MilestoneConfig},
+    plus_minus::PlusMinus,
+    *,
+};
 use std::{
-    path::{Path, PathBuf},
-    time::Duration,
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime},
 };
 
 use structopt::StructOpt;
+
+/// The most milestones to add to the live rotation at once
+const MAX_MILESTONE_SCREENS: usize = 2;
+
 #[derive(StructOpt)]
 struct Args {
     /// The 3 letter team code to look for
@@ -14,6 +32,115 @@ struct Args {
     /// The amount of time to wait between checking
     #[structopt(default_value = "5")]
     pub seconds: u64,
+    /// The kinds of plays to show during a live game (e.g. points,
+    /// foul, freeThrow), defaults to everything but substitutions,
+    /// timeouts and stoppages
+    #[structopt(long = "play-kind", number_of_values = 1)]
+    pub play_kinds: Vec<ActionKind>,
+    /// Only show plays by these teams during a live game
+    #[structopt(long = "play-team", number_of_values = 1)]
+    pub play_teams: Vec<String>,
+    /// Only show made shots and free throws during a live game
+    #[structopt(long)]
+    pub scoring_only: bool,
+    /// Only show plays by the team being followed during a live game
+    #[structopt(long)]
+    pub our_plays_only: bool,
+    /// Show scoring runs, droughts and lead changes during a live game
+    #[structopt(long)]
+    pub momentum: bool,
+    /// Show team fouls, the bonus and foul trouble during a live game
+    #[structopt(long)]
+    pub fouls: bool,
+    /// The box score categories to rotate through after a game, each
+    /// with the number of players to list (e.g. "pts:3,reb:1" or
+    /// points, fastBreakPoints, plusMinus), the e-ink display only has
+    /// room for one. Defaults to the team's leaders in assists, blocks,
+    /// fouls, steals, turnovers, points, threes and rebounds
+    #[structopt(long = "stat", number_of_values = 1, use_delimiter = true)]
+    pub stats: Vec<RotationEntry>,
+    /// Include a screen with the players on court during a live game
+    #[structopt(long)]
+    pub lineups: bool,
+    /// Include a screen comparing the two teams' totals during
+    /// a live game, it is always shown after a game
+    #[structopt(long)]
+    pub team_stats: bool,
+    /// Follow a player by name (e.g. "Jayson Tatum" or "Tatum"),
+    /// showing their stat line and last play during a game and
+    /// their line after it
+    #[structopt(long = "player", number_of_values = 1)]
+    pub players: Vec<String>,
+    /// Watch for 30, 40 and 50 point games, triple-doubles, career
+    /// highs and team records during a live game, the points from
+    /// finished games are saved to `out_dir/archive` to look up
+    /// career highs
+    #[structopt(long)]
+    pub milestones: bool,
+    /// The team record for threes in a game, used with --milestones
+    #[structopt(long)]
+    pub franchise_threes: Option<u8>,
+    /// A player on the fantasy roster, scored across every game
+    /// tonight
+    #[structopt(long = "fantasy", number_of_values = 1)]
+    pub fantasy_roster: Vec<String>,
+    /// The fantasy weights (e.g. "pts=1,reb=1.2,ast=1.5,stl=3,blk=3,tov=-1"),
+    /// stats left out keep these defaults
+    #[structopt(long)]
+    pub fantasy_scoring: Option<FantasyScoring>,
+    /// How often to refresh the fantasy scores in seconds, they need
+    /// every game's box score so they are not fetched on every check
+    #[structopt(long, default_value = "60")]
+    pub fantasy_seconds: u64,
+    /// Also draw each screen into `datafile.png` and `datafile.bin`,
+    /// the SSD1675's black/white RAM that can be sent to the panel as
+    /// is, so the display doesn't need Python to render
+    #[structopt(long)]
+    pub bitmap: bool,
+    /// Show both teams' leaders side by side after a game instead
+    /// of only the team being followed
+    #[structopt(long)]
+    pub compare: bool,
+}
+
+impl Args {
+    fn play_filter(&self) -> PlayFilter {
+        let mut filter = PlayFilter::default();
+        if !self.play_kinds.is_empty() {
+            filter.kinds = self.play_kinds.clone();
+        }
+        filter.teams = self.play_teams.clone();
+        filter.scoring_only = self.scoring_only;
+        filter.our_team_only = self.our_plays_only;
+        filter
+    }
+
+    fn stat_categories(&self) -> Vec<RotationEntry> {
+        if self.stats.is_empty() {
+            StatCategory::DEFAULT_ROTATION
+                .iter()
+                .map(|&category| RotationEntry::from(category))
+                .collect()
+        } else {
+            self.stats.clone()
+        }
+    }
+
+    fn archive_dir(&self) -> PathBuf {
+        self.out_dir.join("archive")
+    }
+
+    fn fantasy_scoring(&self) -> FantasyScoring {
+        self.fantasy_scoring.clone().unwrap_or_default()
+    }
+
+    fn momentum_config(&self) -> Option<MomentumConfig> {
+        if self.momentum {
+            Some(MomentumConfig::default())
+        } else {
+            None
+        }
+    }
 }
 
 #[tokio::main]
@@ -23,51 +150,159 @@ async fn main() {
     if !args.out_dir.exists() {
         std::fs::create_dir_all(&args.out_dir).unwrap();
     }
+    let mut frame = 0usize;
+    let mut state = State::default();
     loop {
-        tick(&args.team, args.out_dir.join("datafile"), args.seconds).await;
+        tick(&args, frame, &mut state).await;
+        frame = frame.wrapping_add(1);
         tokio::time::sleep(Duration::from_secs(args.seconds)).await;
     }
 }
 
-async fn tick(team: &str, file: impl AsRef<Path>, tick: u64) {
-    if let Some(today) = find_game_today(team).await {
-        let mut game = render::game(&today);
+/// Write the current screen to the data file, `frame` counts the
+/// ticks so far and picks which screen to show when a live game
+/// has more than one
+async fn tick(args: &Args, frame: usize, state: &mut State) {
+    let team = args.team.as_str();
+    if let Some(mut today) = find_game_today(team).await {
         if today.has_ended() {
-            game_ended(today, team, file.as_ref(), tick).await;
+            game_ended(args, today, &mut state.fantasy).await;
             return;
         }
-        if today.is_active() {
-            let plays = get_play_by_play(
+        let plays = if today.is_active() {
+            get_play_by_play(
                 &today.id.to_string(),
                 &today.home.tri_code,
                 &today.away.tri_code,
             )
             .await
-            .unwrap_or_else(Vec::new);
-            if let Some(last_play) = plays.last() {
-                game += &render::action(&last_play)
+            .unwrap_or_else(Vec::new)
+        } else {
+            Vec::new()
+        };
+        today.possession = plays
+            .last()
+            .and_then(|p| p.possession())
+            .map(|id| StringOrNumber::String(id.to_string()));
+        let mut game = render::game(&today);
+        if today.is_active() {
+            let at_break = today.is_break(&plays);
+            let box_scores = if at_break
+                || args.lineups
+                || args.team_stats
+                || args.milestones
+                || !args.players.is_empty()
+            {
+                get_game_boxscore(&today.id.to_string()).await
+            } else {
+                None
+            };
+            let body: Vec<_> = if at_break {
+                render::period_summary(&today, &plays)
+                    .into_iter()
+                    .map(|line| (line, FitPolicy::SMALL))
+                    .collect()
+            } else if let Some(last_play) = args.play_filter().pick(&plays, team) {
+                vec![(render::action(last_play), FitPolicy::WRAP)]
+            } else {
+                Vec::new()
+            };
+            // the extras are dropped from the bottom up when the
+            // screen runs out of height
+            let mut extras = Vec::new();
+            if let Some(config) = args.momentum_config() {
+                let momentum = Momentum::from_plays(
+                    &plays,
+                    &today.home.tri_code,
+                    &today.away.tri_code,
+                    &config,
+                );
+                extras.extend(render::momentum(&momentum));
             }
+            if args.fouls {
+                let fouls = FoulTracker::from_plays(&plays);
+                for alert in fouls.recent_alerts() {
+                    log::debug!("foul alert: {}", alert);
+                }
+                extras.extend(render::fouls(&fouls, &today));
+            }
+            let extras = extras
+                .into_iter()
+                .map(|line| (line, FitPolicy::SMALL))
+                .collect();
+            let base = game;
+            game = fit::compose(&base, body, extras);
+            let mut screens = vec![game];
+            if at_break {
+                screens.push(render::linescore(&today));
+                screens.extend(box_scores.as_ref().map(render::period_leaders));
+            }
+            if let Some(box_scores) = box_scores.as_ref() {
+                if args.lineups {
+                    let lineups = LineupTracker::from_plays(box_scores, &plays);
+                    let body = render::lineups(&lineups)
+                        .into_iter()
+                        .map(|line| (line, FitPolicy::SMALL))
+                        .collect();
+                    screens.push(fit::compose("", body, Vec::new()));
+                }
+                if args.team_stats {
+                    screens.extend(render::team_comparison(box_scores));
+                }
+                for player in args
+                    .players
+                    .iter()
+                    .filter_map(|p| box_scores.find_player(p))
+                {
+                    let last_play = plays
+                        .iter()
+                        .rev()
+                        .find(|play| play.person_id() == Some(player.person_id));
+                    let body = render::player(player, last_play)
+                        .into_iter()
+                        .map(|line| (line, FitPolicy::WRAP))
+                        .collect();
+                    screens.push(fit::compose(&base, body, Vec::new()));
+                }
+                if let Some(config) = state.milestones.config(args) {
+                    let alerts = Milestone::watch(box_scores, config);
+                    for alert in &alerts {
+                        log::debug!("milestone: {}", alert);
+                    }
+                    for alert in alerts.iter().take(MAX_MILESTONE_SCREENS) {
+                        let body = render::milestone(alert, &today)
+                            .into_iter()
+                            .map(|line| (line, FitPolicy::SMALL))
+                            .collect();
+                        screens.push(fit::compose(&base, body, Vec::new()));
+                    }
+                }
+            }
+            screens.extend(state.fantasy.screens(args).await.iter().cloned());
+            game = screens.swap_remove(frame % screens.len());
         }
 
-        std::fs::write(file, &game).unwrap();
+        write_screen(args, &game);
         log::debug!("updating today's game info");
         return;
     }
     if let Some(last) = find_last_game(team).await {
         log::debug!("updating last game info");
-        game_ended(last, team, file.as_ref(), tick).await;
+        game_ended(args, last, &mut state.fantasy).await;
         return;
     }
     if let Some(next) = find_next_game(team).await {
         log::debug!("updating next game info");
-        std::fs::write(file, &render::game(&next)).unwrap();
+        write_screen(args, &render::game(&next));
         return;
     }
-    std::fs::write(file, Line::large("No Game Found").render()).unwrap();
+    write_screen(args, &Line::large("No Game Found").render());
     log::warn!("no next game found");
 }
 
-async fn game_ended(game: Game, team: &str, file: impl AsRef<Path>, tick: u64) {
+async fn game_ended(args: &Args, game: Game, fantasy: &mut FantasyCache) {
+    let team = args.team.as_str();
+    let categories = args.stat_categories();
     let base = render::game(&game);
     let next_game = find_next_game(team).await;
     let next = next_game
@@ -79,76 +314,213 @@ async fn game_ended(game: Game, team: &str, file: impl AsRef<Path>, tick: u64) {
     } else {
         String::new()
     };
-    let mut box_score = get_game_boxscore(&game.id.to_string()).await.map(|b| {
-        log::debug!("found box scores");
-        if b.home.abv.eq_ignore_ascii_case(team) {
-            b.home.box_score
-        } else {
-            b.away.box_score
-        }
-    });
-    let mut box_score_idxs = box_score.as_ref().map(get_indexes).map(|idxs| idxs.into_iter().cycle());
+    let linescore = render::linescore(&game);
+    let mut rotation = postgame_rotation(&game, args, &categories).await;
+    let mut idx = 0;
     while chrono::Utc::now() < next {
-        let mut buffer = base.clone();
-        if let Some(box_score) = box_score.as_ref() {
-            if let Some(iter) = box_score_idxs.as_mut() {
-                if let Some(idx) = iter.next() {
-                    if let Some(box_info) = render::box_score(box_score, idx) {
-                        buffer += &box_info;
-                    }
-                }
-            }
-        } else if let Some(b) = get_game_boxscore(&game.id.to_string()).await {
-            box_score = if b.home.abv.eq_ignore_ascii_case(team) {
-                Some(b.home.box_score)
-            } else {
-                Some(b.away.box_score)
-            };
-            box_score_idxs = box_score.as_ref().map(get_indexes).map(|idxs| idxs.into_iter().cycle());
+        if rotation.lines.is_empty() {
+            rotation = postgame_rotation(&game, args, &categories).await;
         }
-        std::fs::write(file.as_ref(), format!("{}{}", buffer, trailer)).unwrap();
-        tokio::time::sleep(Duration::from_secs(tick)).await;
+        let mut screens: Vec<String> = rotation
+            .lines
+            .iter()
+            .map(|line| format!("{}{}{}", base, line, trailer))
+            .collect();
+        if screens.is_empty() {
+            screens.push(format!("{}{}", base, trailer));
+        }
+        screens.push(linescore.clone());
+        screens.extend(rotation.pages.iter().cloned());
+        // other games are still going
+        screens.extend(fantasy.screens(args).await.iter().cloned());
+        write_screen(args, &screens[idx % screens.len()]);
+        idx += 1;
+        tokio::time::sleep(Duration::from_secs(args.seconds)).await;
     }
-
 }
 
-fn get_indexes(box_scores: &nba_eink_disp::box_score::BoxScore) -> Vec<u8> {
-    let mut ret = Vec::new();
-    if box_scores.assist.is_some() {
-        ret.push(0);
-    }
-    if box_scores.blocks.is_some() {
-        ret.push(1);
-    }
-    if box_scores.fouled.is_some() {
-        ret.push(2);
+/// Write `screen` to the data file, and the bitmaps when enabled
+fn write_screen(args: &Args, screen: &str) {
+    let file = args.out_dir.join("datafile");
+    std::fs::write(&file, screen).unwrap();
+    if !args.bitmap {
+        return;
     }
-    if box_scores.fouler.is_some() {
-        ret.push(3);
+    let renderer = match bitmap::Renderer::shared() {
+        Some(renderer) => renderer,
+        None => return,
+    };
+    let lines: Vec<_> = screen.lines().filter_map(Line::parse).collect();
+    let mut panel = Ssd1675Buffer::default();
+    renderer
+        .draw(&lines, &mut panel)
+        .unwrap_or_else(|e| match e {});
+    if let Err(e) = std::fs::write(file.with_extension("bin"), panel.as_bytes()) {
+        log::error!("failed to write the panel buffer: {}", e);
     }
-    if box_scores.steals.is_some() {
-        ret.push(4);
+    let bitmap = renderer.render(&lines);
+    match bitmap.to_png() {
+        Ok(png) => {
+            if let Err(e) = std::fs::write(file.with_extension("png"), png) {
+                log::error!("failed to write png: {}", e);
+            }
+        }
+        Err(e) => log::error!("failed to encode png: {}", e),
     }
-    if box_scores.turnovers.is_some() {
-        ret.push(5);
+}
+
+/// The fantasy roster's scores from every game today that has
+/// started, the box scores are fetched at the same time
+async fn fantasy_screens(args: &Args) -> Vec<String> {
+    if args.fantasy_roster.is_empty() {
+        return Vec::new();
     }
-    if box_scores.points.is_some() {
-        ret.push(6);
+    let games = find_games_today().await.unwrap_or_default();
+    let requests: Vec<_> = games
+        .iter()
+        .filter(|g| g.has_started())
+        .map(|g| {
+            let id = g.id.to_string();
+            tokio::spawn(async move { get_game_boxscore(&id).await })
+        })
+        .collect();
+    let mut box_scores: Vec<GameBoxScores> = Vec::new();
+    for request in requests {
+        match request.await {
+            Ok(Some(b))
+                if args
+                    .fantasy_roster
+                    .iter()
+                    .any(|p| b.find_player(p).is_some()) =>
+            {
+                box_scores.push(b)
+            }
+            Ok(_) => {}
+            Err(e) => log::error!("failed to fetch a box score: {}", e),
+        }
     }
-    if box_scores.paint_points.is_some() {
-        ret.push(7);
+    let total =
+        FantasyTotal::from_box_scores(&args.fantasy_roster, &box_scores, &args.fantasy_scoring());
+    render::fantasy(&total)
+}
+
+/// What is kept from one check to the next
+#[derive(Default)]
+struct State {
+    fantasy: FantasyCache,
+    milestones: MilestoneCache,
+}
+
+/// The [`MilestoneConfig`], read again only when the archive has
+/// changed since
+#[derive(Default)]
+struct MilestoneCache {
+    config: Option<MilestoneConfig>,
+    modified: Option<SystemTime>,
+}
+
+impl MilestoneCache {
+    fn config(&mut self, args: &Args) -> Option<&MilestoneConfig> {
+        if !args.milestones {
+            return None;
+        }
+        let dir = args.archive_dir();
+        let modified = Archive::modified(&dir);
+        if self.config.is_none() || modified != self.modified {
+            log::debug!("reading the milestone archive");
+            self.config = Some(MilestoneConfig::from_archive(
+                &dir,
+                &args.team,
+                args.franchise_threes,
+            ));
+            self.modified = modified;
+        }
+        self.config.as_ref()
     }
-    if box_scores.threes.is_some() {
-        ret.push(8);
+}
+
+/// The last [`fantasy_screens`], refreshed every `--fantasy-seconds`
+/// rather than on every check
+#[derive(Default)]
+struct FantasyCache {
+    screens: Vec<String>,
+    refreshed: Option<Instant>,
+}
+
+impl FantasyCache {
+    async fn screens(&mut self, args: &Args) -> &[String] {
+        let interval = Duration::from_secs(args.fantasy_seconds);
+        if self
+            .refreshed
+            .map(|at| at.elapsed() >= interval)
+            .unwrap_or(true)
+        {
+            self.screens = fantasy_screens(args).await;
+            self.refreshed = Some(Instant::now());
+        }
+        &self.screens
     }
-    if box_scores.rebounds.is_some() {
-        ret.push(9);
+}
+
+/// What to cycle through after a game, both empty if the box
+/// score isn't available yet
+#[derive(Default)]
+struct Postgame {
+    /// Lines shown under the final score
+    lines: Vec<String>,
+    /// Screens shown on their own
+    pages: Vec<String>,
+}
+
+async fn postgame_rotation(game: &Game, args: &Args, categories: &[RotationEntry]) -> Postgame {
+    let team = args.team.as_str();
+    let box_scores = if let Some(b) = get_game_boxscore(&game.id.to_string()).await {
+        log::debug!("found box scores");
+        b
+    } else {
+        return Postgame::default();
+    };
+    let ours = if box_scores.home.abv.eq_ignore_ascii_case(team) {
+        &box_scores.home
+    } else {
+        &box_scores.away
+    };
+    let mut ret: Vec<String> = categories
+        .iter()
+        .filter_map(|entry| {
+            if args.compare {
+                render::leader_comparison(&box_scores, entry.category, entry.top)
+            } else {
+                render::box_score(&ours.box_score, entry.category, entry.top)
+            }
+        })
+        .collect();
+    if let Some(plays) = get_play_by_play(
+        &game.id.to_string(),
+        &game.home.tri_code,
+        &game.away.tri_code,
+    )
+    .await
+    {
+        let plus_minus = PlusMinus::from_plays(&box_scores, &plays);
+        if let Some(best) = plus_minus.best_lineup(&ours.abv, GameClock::from_secs(2 * 60)) {
+            ret.push(render::best_lineup(best));
+        }
     }
-    if box_scores.off_rebounds.is_some() {
-        ret.push(10);
+    if args.milestones {
+        if let Err(e) = milestones::archive(&args.archive_dir(), &box_scores) {
+            log::error!("failed to archive box score: {}", e);
+        }
     }
-    if box_scores.def_rebounds.is_some() {
-        ret.push(11);
+    ret.push(render::bench_points(ours));
+    for player in args.players.iter() {
+        match box_scores.find_player(player) {
+            Some(player) => ret.push(render::player_line(player)),
+            None => log::warn!("{} isn't in the box score", player),
+        }
     }
-    ret
+    let mut pages = render::box_score_table(ours);
+    pages.extend(render::inactive(ours));
+    pages.extend(render::team_comparison(&box_scores));
+    Postgame { lines: ret, pages }
 }
@@ -0,0 +1,405 @@
+use std::{collections::HashMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::box_score::{GameBoxScores, StatPlayer, TeamBoxScore};
+
+/// The scoring games worth calling out
+pub const POINT_MILESTONES: [u8; 3] = [30, 40, 50];
+/// How close a player needs to be to a points milestone or a
+/// career high before it is reported
+pub const POINTS_WATCH: u8 = 5;
+/// The fewest points an archived career high needs to be worth
+/// watching, the archive only covers the games seen so far
+pub const CAREER_HIGH_MIN: u8 = 20;
+/// The lowest of a player's three best categories for a
+/// triple-double to be on watch
+pub const TRIPLE_DOUBLE_WATCH: u8 = 7;
+/// How close a player needs to be to the franchise record
+/// for threes
+pub const THREES_WATCH: u8 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MilestoneConfig {
+    /// The most points each player has scored in a game, by
+    /// person id
+    pub career_highs: HashMap<u64, u8>,
+    /// The most threes made in a game by a player on the team
+    /// being followed
+    pub franchise_threes: Option<u8>,
+    /// The team the franchise record belongs to
+    pub team: String,
+}
+
+impl MilestoneConfig {
+    /// Take career highs from the games archived after previous
+    /// games, see [`archive`]
+    pub fn from_archive(dir: &Path, team: &str, franchise_threes: Option<u8>) -> Self {
+        Self {
+            career_highs: career_highs(dir),
+            franchise_threes,
+            team: team.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum Milestone {
+    /// Closing in on or past one of [`POINT_MILESTONES`]
+    Points {
+        team: String,
+        name: String,
+        points: u8,
+        target: u8,
+    },
+    TripleDouble {
+        team: String,
+        name: String,
+        points: u8,
+        rebounds: u8,
+        assists: u8,
+        reached: bool,
+    },
+    CareerHigh {
+        team: String,
+        name: String,
+        points: u8,
+        career_high: u8,
+    },
+    FranchiseThrees {
+        team: String,
+        name: String,
+        threes: u8,
+        record: u8,
+    },
+}
+
+impl std::fmt::Display for Milestone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, {}", self.headline(), self.detail())
+    }
+}
+
+impl Milestone {
+    /// The player and the stat the milestone is about
+    pub fn headline(&self) -> String {
+        match self {
+            Self::Points { name, points, .. } | Self::CareerHigh { name, points, .. } => {
+                format!("{} {} pts", name, points)
+            }
+            Self::TripleDouble {
+                name,
+                points,
+                rebounds,
+                assists,
+                ..
+            } => format!("{} {}-{}-{}", name, points, rebounds, assists),
+            Self::FranchiseThrees { name, threes, .. } => format!("{} {} threes", name, threes),
+        }
+    }
+
+    /// What the player reached or how far they are from it
+    pub fn detail(&self) -> String {
+        match self {
+            Self::Points { points, target, .. } => {
+                if points >= target {
+                    format!("{}-pt game", target)
+                } else {
+                    format!("{} from {}", target - points, target)
+                }
+            }
+            Self::TripleDouble { reached, .. } => {
+                if *reached {
+                    "triple-double".to_string()
+                } else {
+                    "triple-double watch".to_string()
+                }
+            }
+            Self::CareerHigh {
+                points,
+                career_high,
+                ..
+            } => {
+                if points > career_high {
+                    "career high".to_string()
+                } else {
+                    format!("career high {}", career_high)
+                }
+            }
+            Self::FranchiseThrees { threes, record, .. } => {
+                if threes > record {
+                    "team record".to_string()
+                } else {
+                    format!("team record {}", record)
+                }
+            }
+        }
+    }
+
+    /// Every milestone in reach for the players in `box_scores`, the
+    /// rarer milestones first
+    pub fn watch(box_scores: &GameBoxScores, config: &MilestoneConfig) -> Vec<Milestone> {
+        let mut ret: Vec<_> = [&box_scores.home, &box_scores.away]
+            .iter()
+            .flat_map(|team| team.players.iter().map(move |p| (*team, p)))
+            .flat_map(|(team, player)| Self::for_player(team, player, config))
+            .collect();
+        ret.sort_by_key(Milestone::rarity);
+        ret
+    }
+
+    fn for_player(team: &TeamBoxScore, player: &StatPlayer, config: &MilestoneConfig) -> Vec<Self> {
+        let stats = &player.statistics;
+        // the family name alone keeps the lines short
+        let name = if player.family_name.is_empty() {
+            player.player_name.clone()
+        } else {
+            player.family_name.clone()
+        };
+        let team = team.abv.clone();
+        let mut ret = Vec::new();
+        let career_high = config
+            .career_highs
+            .get(&player.person_id)
+            .copied()
+            .filter(|&high| {
+                high >= CAREER_HIGH_MIN && stats.points.saturating_add(POINTS_WATCH) >= high
+            });
+        if let Some(career_high) = career_high {
+            ret.push(Self::CareerHigh {
+                team: team.clone(),
+                name: name.clone(),
+                points: stats.points,
+                career_high,
+            });
+        }
+        if let Some(record) = config.franchise_threes {
+            if team.eq_ignore_ascii_case(&config.team)
+                && stats.three_pointers_made > 0
+                && stats.three_pointers_made.saturating_add(THREES_WATCH) >= record
+            {
+                ret.push(Self::FranchiseThrees {
+                    team: team.clone(),
+                    name: name.clone(),
+                    threes: stats.three_pointers_made,
+                    record,
+                });
+            }
+        }
+        let mut best = [
+            stats.points,
+            stats.rebounds_total,
+            stats.assists,
+            stats.steals,
+            stats.blocks,
+        ];
+        best.sort_unstable_by(|lhs, rhs| rhs.cmp(lhs));
+        if best[2] >= TRIPLE_DOUBLE_WATCH {
+            ret.push(Self::TripleDouble {
+                team: team.clone(),
+                name: name.clone(),
+                points: stats.points,
+                rebounds: stats.rebounds_total,
+                assists: stats.assists,
+                reached: best[2] >= 10,
+            });
+        }
+        let next = POINT_MILESTONES.iter().find(|&&t| stats.points < t);
+        let reached = POINT_MILESTONES.iter().rev().find(|&&t| stats.points >= t);
+        let target = match (next, reached) {
+            (Some(&next), _) if stats.points.saturating_add(POINTS_WATCH) >= next => Some(next),
+            (_, Some(&reached)) => Some(reached),
+            _ => None,
+        };
+        // a career high already says how many points they have
+        if let (Some(target), None) = (target, career_high) {
+            ret.push(Self::Points {
+                team,
+                name,
+                points: stats.points,
+                target,
+            });
+        }
+        ret
+    }
+
+    /// If the player has already got there, the rest are still
+    /// being watched
+    pub fn is_reached(&self) -> bool {
+        match self {
+            Self::Points { points, target, .. } => points >= target,
+            Self::TripleDouble { reached, .. } => *reached,
+            Self::CareerHigh {
+                points,
+                career_high,
+                ..
+            } => points > career_high,
+            Self::FranchiseThrees { threes, record, .. } => threes > record,
+        }
+    }
+
+    /// Lower is rarer
+    fn rarity(&self) -> u8 {
+        match self {
+            Self::FranchiseThrees { .. } => 0,
+            Self::CareerHigh { .. } => 1,
+            Self::TripleDouble { .. } => 2,
+            Self::Points { .. } => 3,
+        }
+    }
+}
+
+/// The file in the archive directory with the career highs
+pub const ARCHIVE_FILE: &str = "career_highs.json";
+
+/// What is kept from finished games, only what later games
+/// look back at
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Archive {
+    /// The games already counted
+    pub games: Vec<String>,
+    /// The most points each player has scored in a game, by
+    /// person id
+    pub career_highs: HashMap<u64, u8>,
+}
+
+impl Archive {
+    /// Read the archive in `dir`, empty if there isn't one yet
+    pub fn load(dir: &Path) -> Self {
+        let path = dir.join(ARCHIVE_FILE);
+        let json = match std::fs::read_to_string(&path) {
+            Ok(json) => json,
+            Err(e) => {
+                log::debug!("no archive at {}: {}", path.display(), e);
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&json).unwrap_or_else(|e| {
+            log::warn!("ignoring invalid archive {}: {}", path.display(), e);
+            Self::default()
+        })
+    }
+
+    /// When the archive in `dir` was last written, `None` if there
+    /// isn't one
+    pub fn modified(dir: &Path) -> Option<std::time::SystemTime> {
+        std::fs::metadata(dir.join(ARCHIVE_FILE))
+            .and_then(|m| m.modified())
+            .ok()
+    }
+
+    /// Count a finished game, returns `false` if it was already
+    /// counted
+    pub fn add(&mut self, box_scores: &GameBoxScores) -> bool {
+        if self.games.contains(&box_scores.id) {
+            return false;
+        }
+        self.games.push(box_scores.id.clone());
+        for player in box_scores
+            .home
+            .players
+            .iter()
+            .chain(&box_scores.away.players)
+        {
+            let high = self.career_highs.entry(player.person_id).or_insert(0);
+            *high = (*high).max(player.statistics.points);
+        }
+        true
+    }
+}
+
+/// Add a finished game to the archive in `dir` so later games can
+/// look back at it, games already counted are left alone
+pub fn archive(dir: &Path, box_scores: &GameBoxScores) -> std::io::Result<()> {
+    let mut archive = Archive::load(dir);
+    if !archive.add(box_scores) {
+        return Ok(());
+    }
+    std::fs::create_dir_all(dir)?;
+    let json = serde_json::to_string(&archive)?;
+    std::fs::write(dir.join(ARCHIVE_FILE), json)
+}
+
+/// The most points each player has scored in an archived game
+pub fn career_highs(dir: &Path) -> HashMap<u64, u8> {
+    Archive::load(dir).career_highs
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        bitmap::{Renderer, MARGIN, SMALL_PX, WIDTH},
+        test_util::{box_scores, player},
+    };
+
+    fn scorer(id: u64, points: u8) -> serde_json::Value {
+        let mut p = player(id, "G. Antetokounmpo", true, json!({ "points": points }));
+        p["familyName"] = json!("Antetokounmpo");
+        p
+    }
+
+    #[test]
+    fn archive_keeps_career_highs() {
+        let dir = std::env::temp_dir().join(format!("milestones-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(career_highs(&dir).is_empty());
+        let mut game = box_scores(vec![scorer(1, 41)], vec![scorer(2, 12)]);
+        archive(&dir, &game).unwrap();
+        game.id = "0022300002".to_string();
+        game.home.players[0].statistics.points = 33;
+        archive(&dir, &game).unwrap();
+        // the same game again is only counted once
+        game.away.players[0].statistics.points = 50;
+        archive(&dir, &game).unwrap();
+        let highs = career_highs(&dir);
+        assert_eq!(highs.get(&1), Some(&41));
+        assert_eq!(highs.get(&2), Some(&12));
+        assert_eq!(Archive::load(&dir).games.len(), 2);
+        assert!(Archive::modified(&dir).is_some());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn watch_near_a_career_high() {
+        let config = MilestoneConfig {
+            career_highs: vec![(1, 44)].into_iter().collect(),
+            franchise_threes: None,
+            team: "BOS".to_string(),
+        };
+        let game = box_scores(vec![scorer(1, 41)], vec![scorer(2, 27)]);
+        let alerts = Milestone::watch(&game, &config);
+        let text: Vec<_> = alerts.iter().map(Milestone::to_string).collect();
+        assert_eq!(
+            text,
+            vec![
+                "Antetokounmpo 41 pts, career high 44",
+                "Antetokounmpo 27 pts, 3 from 30",
+            ]
+        );
+        assert!(alerts.iter().all(|alert| !alert.is_reached()));
+    }
+
+    #[test]
+    fn lines_fit_without_shortening() {
+        let alert = Milestone::TripleDouble {
+            team: "MIL".to_string(),
+            name: "Antetokounmpo".to_string(),
+            points: 38,
+            rebounds: 14,
+            assists: 9,
+            reached: false,
+        };
+        let renderer = Renderer::shared().unwrap();
+        let room = WIDTH as i32 - 2 * MARGIN;
+        for line in &[alert.headline(), alert.detail()] {
+            let width = renderer.text_width(line, SMALL_PX);
+            assert!(width <= room, "{} is {}px", line, width);
+        }
+    }
+}
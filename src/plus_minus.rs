@@ -0,0 +1,337 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    action::Action,
+    box_score::GameBoxScores,
+    clock::GameClock,
+    lineup::{Lineup, LineupTracker, OnCourt},
+};
+
+/// The score and time for one five-man lineup across all of
+/// its stints on the floor
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LineupPlusMinus {
+    pub team: String,
+    pub players: Vec<OnCourt>,
+    pub points_for: u16,
+    pub points_against: u16,
+    pub time: GameClock,
+    pub stints: u16,
+}
+
+impl LineupPlusMinus {
+    fn key(&self) -> impl Iterator<Item = u64> + '_ {
+        self.players.iter().map(|p| p.person_id)
+    }
+
+    pub fn plus_minus(&self) -> i32 {
+        i32::from(self.points_for) - i32::from(self.points_against)
+    }
+}
+
+/// One unbroken stretch on the floor for a five-man lineup
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Stint {
+    pub team: String,
+    /// Sorted by person id
+    pub players: Vec<OnCourt>,
+    pub period: u8,
+    /// The game time elapsed when the lineup came on
+    pub start: GameClock,
+    /// The game time elapsed when the lineup went off
+    pub end: GameClock,
+    pub points_for: u16,
+    pub points_against: u16,
+}
+
+impl Stint {
+    fn new(lineup: &Lineup, period: u8, start: GameClock) -> Self {
+        let mut players = lineup.players.clone();
+        players.sort_by_key(|p| p.person_id);
+        Self {
+            team: lineup.team.clone(),
+            players,
+            period,
+            start,
+            end: start,
+            points_for: 0,
+            points_against: 0,
+        }
+    }
+
+    pub fn time(&self) -> GameClock {
+        self.end - self.start
+    }
+
+    pub fn plus_minus(&self) -> i32 {
+        i32::from(self.points_for) - i32::from(self.points_against)
+    }
+
+    /// A stint with no time and no points, left behind when two
+    /// substitutions or a period start follow each other
+    fn is_empty(&self) -> bool {
+        self.time() == GameClock::default() && self.points_for + self.points_against == 0
+    }
+
+    fn contains(&self, person_id: u64) -> bool {
+        self.players.iter().any(|p| p.person_id == person_id)
+    }
+
+    fn key(&self) -> impl Iterator<Item = u64> + '_ {
+        self.players.iter().map(|p| p.person_id)
+    }
+}
+
+/// The score and time for one player while on and off the floor
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerPlusMinus {
+    pub team: String,
+    pub player: OnCourt,
+    pub points_for: u16,
+    pub points_against: u16,
+    pub time: GameClock,
+    pub off_points_for: u16,
+    pub off_points_against: u16,
+    pub off_time: GameClock,
+}
+
+impl PlayerPlusMinus {
+    pub fn plus_minus(&self) -> i32 {
+        i32::from(self.points_for) - i32::from(self.points_against)
+    }
+
+    /// The team's plus-minus while the player sat
+    pub fn off_plus_minus(&self) -> i32 {
+        i32::from(self.off_points_for) - i32::from(self.off_points_against)
+    }
+
+    /// How much better the team did with the player on the floor
+    pub fn on_off(&self) -> i32 {
+        self.plus_minus() - self.off_plus_minus()
+    }
+}
+
+/// Plus-minus for every stint, lineup and player computed from
+/// the score progression of the play-by-play
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PlusMinus {
+    /// In the order they happened, both teams interleaved
+    pub stints: Vec<Stint>,
+    pub lineups: Vec<LineupPlusMinus>,
+    pub players: Vec<PlayerPlusMinus>,
+}
+
+impl PlusMinus {
+    /// Replay `plays`, which are expected to be sorted by action
+    /// number, crediting each change in score and each tick of the
+    /// clock to the lineups on the floor at the time
+    pub fn from_plays(box_scores: &GameBoxScores, plays: &[Action]) -> Self {
+        let mut tracker = LineupTracker::from_starters(box_scores);
+        let mut stints = Vec::new();
+        let (mut home, mut away) = (0u16, 0u16);
+        let mut last = GameClock::default();
+        let mut home_stint = Stint::new(&tracker.home, 1, last);
+        let mut away_stint = Stint::new(&tracker.away, 1, last);
+        for (idx, play) in plays.iter().enumerate() {
+            let now = play.elapsed().max(last);
+            last = now;
+            let scored_home = play.home_score().saturating_sub(home);
+            let scored_away = play.away_score().saturating_sub(away);
+            home = home.max(play.home_score());
+            away = away.max(play.away_score());
+            credit(&mut home_stint, scored_home, scored_away, now);
+            credit(&mut away_stint, scored_away, scored_home, now);
+            if tracker.step(plays, idx) {
+                let period = play.quarter();
+                for (stint, lineup) in [
+                    (&mut home_stint, &tracker.home),
+                    (&mut away_stint, &tracker.away),
+                ] {
+                    if stint.key().ne(lineup.key()) {
+                        let next = Stint::new(lineup, period, now);
+                        stints.push(std::mem::replace(stint, next));
+                    }
+                }
+            }
+        }
+        stints.push(home_stint);
+        stints.push(away_stint);
+        // a substitution is logged as an out and an in, drop the
+        // partial lineups between the two that never saw any action
+        stints.retain(|s| !s.is_empty());
+        for stint in stints.iter().filter(|s| s.players.len() != 5) {
+            log::warn!(
+                "{} played {} with {} players",
+                stint.team,
+                stint.time(),
+                stint.players.len()
+            );
+        }
+        let lineups = lineups_from_stints(&stints);
+        let players = players_from_stints(&stints);
+        Self {
+            stints,
+            lineups,
+            players,
+        }
+    }
+
+    /// The lineup with the best plus-minus for `team` that played at
+    /// least `min_time`, ties go to the lineup that played longer
+    pub fn best_lineup(&self, team: &str, min_time: GameClock) -> Option<&LineupPlusMinus> {
+        self.lineups
+            .iter()
+            .filter(|l| l.team.eq_ignore_ascii_case(team) && l.time >= min_time)
+            .max_by_key(|l| (l.plus_minus(), l.time))
+    }
+
+    pub fn player(&self, person_id: u64) -> Option<&PlayerPlusMinus> {
+        self.players
+            .iter()
+            .find(|p| p.player.person_id == person_id)
+    }
+
+    /// Every stint `person_id` was part of, in order
+    pub fn player_stints(&self, person_id: u64) -> impl Iterator<Item = &Stint> {
+        self.stints.iter().filter(move |s| s.contains(person_id))
+    }
+}
+
+fn credit(stint: &mut Stint, points_for: u16, points_against: u16, now: GameClock) {
+    stint.points_for += points_for;
+    stint.points_against += points_against;
+    stint.end = now;
+}
+
+fn lineups_from_stints(stints: &[Stint]) -> Vec<LineupPlusMinus> {
+    let mut ret: Vec<LineupPlusMinus> = Vec::new();
+    for stint in stints {
+        let existing = ret
+            .iter_mut()
+            .find(|l| l.team == stint.team && l.key().eq(stint.key()));
+        let entry = if let Some(existing) = existing {
+            existing
+        } else {
+            ret.push(LineupPlusMinus {
+                team: stint.team.clone(),
+                players: stint.players.clone(),
+                points_for: 0,
+                points_against: 0,
+                time: GameClock::default(),
+                stints: 0,
+            });
+            ret.last_mut().unwrap()
+        };
+        entry.points_for += stint.points_for;
+        entry.points_against += stint.points_against;
+        entry.time = entry.time + stint.time();
+        entry.stints += 1;
+    }
+    ret
+}
+
+fn players_from_stints(stints: &[Stint]) -> Vec<PlayerPlusMinus> {
+    let mut ret: Vec<PlayerPlusMinus> = Vec::new();
+    for stint in stints {
+        for player in &stint.players {
+            if !ret.iter().any(|p| p.player.person_id == player.person_id) {
+                ret.push(PlayerPlusMinus {
+                    team: stint.team.clone(),
+                    player: player.clone(),
+                    points_for: 0,
+                    points_against: 0,
+                    time: GameClock::default(),
+                    off_points_for: 0,
+                    off_points_against: 0,
+                    off_time: GameClock::default(),
+                });
+            }
+        }
+    }
+    for entry in &mut ret {
+        let team = entry.team.clone();
+        for stint in stints.iter().filter(|s| s.team == team) {
+            if stint.contains(entry.player.person_id) {
+                entry.points_for += stint.points_for;
+                entry.points_against += stint.points_against;
+                entry.time = entry.time + stint.time();
+            } else {
+                entry.off_points_for += stint.points_for;
+                entry.off_points_against += stint.points_against;
+                entry.off_time = entry.off_time + stint.time();
+            }
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{box_scores, made_shot, roster, substitution, AWAY, HOME};
+
+    /// 6 subs in for 1 for two minutes in which the home team
+    /// is outscored 2-0
+    fn plus_minus() -> PlusMinus {
+        let box_scores = box_scores(roster(1, 6), roster(11, 5));
+        let plays = vec![
+            made_shot(1, "PT11M00.00S", HOME, 2, 0),
+            substitution(2, 1, "PT10M00.00S", 1, "out"),
+            substitution(3, 1, "PT10M00.00S", 6, "in"),
+            made_shot(4, "PT09M00.00S", AWAY, 2, 2),
+            substitution(5, 1, "PT08M00.00S", 6, "out"),
+            substitution(6, 1, "PT08M00.00S", 1, "in"),
+            made_shot(7, "PT07M00.00S", HOME, 4, 2),
+        ];
+        PlusMinus::from_plays(&box_scores, &plays)
+    }
+
+    #[test]
+    fn stints() {
+        let plus_minus = plus_minus();
+        let home: Vec<_> = plus_minus
+            .stints
+            .iter()
+            .filter(|s| s.team == HOME)
+            .collect();
+        assert_eq!(home.len(), 3);
+        assert_eq!(home[1].time(), GameClock::from_secs(120));
+        assert_eq!(home[1].plus_minus(), -2);
+        assert!(plus_minus.stints.iter().all(|s| s.players.len() == 5));
+        assert_eq!(plus_minus.player_stints(6).count(), 1);
+        assert_eq!(plus_minus.player_stints(11).count(), 1);
+    }
+
+    #[test]
+    fn lineups() {
+        let plus_minus = plus_minus();
+        let best = plus_minus.best_lineup(HOME, GameClock::default()).unwrap();
+        assert_eq!(best.stints, 2);
+        assert_eq!(best.plus_minus(), 4);
+        assert_eq!(best.time, GameClock::from_secs(180));
+        assert!(best.players.iter().any(|p| p.person_id == 1));
+        assert!(plus_minus
+            .best_lineup(HOME, GameClock::from_secs(200))
+            .is_none());
+    }
+
+    #[test]
+    fn on_off() {
+        let plus_minus = plus_minus();
+        let starter = plus_minus.player(1).unwrap();
+        assert_eq!(starter.plus_minus(), 4);
+        assert_eq!(starter.off_plus_minus(), -2);
+        assert_eq!(starter.on_off(), 6);
+        assert_eq!(starter.off_time, GameClock::from_secs(120));
+        let bench = plus_minus.player(6).unwrap();
+        assert_eq!(bench.on_off(), -6);
+        let always_on = plus_minus.player(2).unwrap();
+        assert_eq!(always_on.plus_minus(), 2);
+        assert_eq!(always_on.off_time, GameClock::default());
+        assert_eq!(plus_minus.player(11).unwrap().plus_minus(), -2);
+    }
+}
@@ -2,8 +2,15 @@ use std::fmt::Display;
 
 use crate::{
     action::Action,
-    box_score::BoxScore,
-    Game, Line,
+    analysis::{Momentum, ShootingSplits},
+    box_score::{BoxScore, GameBoxScores, Rank, StatCategory, StatPlayer, TeamBoxScore},
+    fantasy::FantasyTotal,
+    fit::{self, FitPolicy},
+    fouls::FoulTracker,
+    lineup::LineupTracker,
+    milestones::Milestone,
+    plus_minus::LineupPlusMinus,
+    Game, Line, Team, CELL_SEPARATOR,
 };
 use chrono::{Local, TimeZone, Utc};
 
@@ -25,40 +32,521 @@ pub fn game(game: &Game) -> String {
 
 fn render_active_game(game: &Game) -> Vec<Line> {
     let mut ret = Vec::new();
-    let time = format!("Q{} {}", game.period.as_number(), game.clock);
-    ret.push(Line::small(time));
+    let time = match game.clock {
+        Some(clock) => format!("Q{} {}", game.period.as_number(), clock),
+        None => format!("Q{}", game.period.as_number()),
+    };
+    // the possession arrow goes with the team's indicators so
+    // the clock stays centered
+    let mut home = team_indicators(&game.home);
+    if game.has_possession(&game.home) {
+        home += " \u{25c0}";
+    }
+    let mut away = team_indicators(&game.away);
+    if game.has_possession(&game.away) {
+        away = format!("\u{25b6} {}", away);
+    }
+    ret.push(Line::small(
+        [home, time, away].join(&CELL_SEPARATOR.to_string()),
+    ));
     ret.push(teams_line(game));
     ret.push(scores_line(game));
     ret
 }
 
-pub fn box_score(info: &BoxScore, idx: u8) -> Option<String> {
-    let (stat, name) = match idx {
-        0 => info.assist.clone().map(|info| (info, "Assists")),
-        1 => info.blocks.clone().map(|info| (info, "Blocks")),
-        2 => info.fouled.clone().map(|info| (info, "Fouled")),
-        3 => info.fouler.clone().map(|info| (info, "Fouler")),
-        4 => info.steals.clone().map(|info| (info, "Steals")),
-        5 => info.turnovers.clone().map(|info| (info, "Turnovers")),
-        6 => info.points.clone().map(|info| (info, "Points")),
-        7 => info.paint_points.clone().map(|info| (info, "Paint Pts")),
-        8 => info.threes.clone().map(|info| (info, "Threes")),
-        9 => info.rebounds.clone().map(|info| (info, "Rebounds(*)")),
-        10 => info.off_rebounds.clone().map(|info| (info, "Rebounds(o)")),
-        11 => info.def_rebounds.clone().map(|info| (info, "Rebounds(d)")),
-        _ => unreachable!(),
-    }?;
+/// Timeout pips followed by a `B` when the team is in the bonus,
+/// kept short enough to share the clock line
+fn team_indicators(team: &Team) -> String {
+    let timeouts = team.timeouts_remaining.unwrap_or(0).max(0) as usize;
+    let mut ret = "\u{2022}".repeat(timeouts.min(7));
+    if team.is_in_bonus() {
+        ret.push('B');
+    }
+    ret
+}
+
+/// Points per quarter with any overtime periods combined
+/// into a single column, followed by the total
+fn linescore_columns(team: &Team) -> Vec<String> {
+    let mut ret = Vec::new();
+    let mut overtime = None;
+    for (i, line) in team.periods.iter().enumerate() {
+        let period = line.period.unwrap_or(i as u8 + 1);
+        let score = line.score.to_string().parse::<u32>().unwrap_or(0);
+        if period <= 4 {
+            ret.push(score.to_string());
+        } else {
+            *overtime.get_or_insert(0) += score;
+        }
+    }
+    if let Some(overtime) = overtime {
+        ret.push(overtime.to_string());
+    }
+    ret.push(team.score.to_string());
+    ret
+}
+
+/// The points per period as a table of cells, one row per team
+pub fn linescore(game: &Game) -> String {
+    let home = linescore_columns(&game.home);
+    let away = linescore_columns(&game.away);
+    let periods = home.len().max(away.len()).saturating_sub(1);
+    let mut header: Vec<String> = (1..=periods.min(4)).map(|p| format!("Q{}", p)).collect();
+    if periods > 4 {
+        header.push("OT".to_string());
+    }
+    header.push("T".to_string());
+    let row = |tri: &str, cols: &[String]| {
+        let mut cells = vec![tri.to_string()];
+        cells.extend(cols.iter().cloned());
+        cells.join(&CELL_SEPARATOR.to_string())
+    };
+    [
+        Line::small(row("", &header)),
+        Line::medium(row(&game.home.tri_code, &home)),
+        Line::medium(row(&game.away.tri_code, &away)),
+    ]
+    .iter()
+    .map(Line::render)
+    .collect()
+}
+
+/// Shown in place of the action line while play is stopped between
+/// periods, a table of each team's score and shooting for the period
+pub fn period_summary(game: &Game, plays: &[Action]) -> Vec<Line> {
+    let period = game.period.as_number();
+    let cells = |cells: Vec<String>| cells.join(&CELL_SEPARATOR.to_string());
+    let mut ret = vec![Line::small(cells(
+        [&format!("Q{}", period), "PTS", "FG", "3P", "FT"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+    ))];
+    for team in [&game.home, &game.away].iter() {
+        let score = team
+            .periods
+            .iter()
+            .enumerate()
+            .find(|(i, l)| l.period.unwrap_or(*i as u8 + 1) == period)
+            .map(|(_, l)| l.score.to_string())
+            .unwrap_or_default();
+        let splits = ShootingSplits::from_plays(plays, &team.tri_code, Some(period));
+        ret.push(Line::small(cells(vec![
+            team.tri_code.clone(),
+            score,
+            format!(
+                "{}/{}",
+                splits.field_goals_made, splits.field_goals_attempted
+            ),
+            format!("{}/{}", splits.threes_made, splits.threes_attempted),
+            format!(
+                "{}/{}",
+                splits.free_throws_made, splits.free_throws_attempted
+            ),
+        ])));
+    }
+    ret
+}
+
+/// The categories on the [`period_leaders`] screen
+const PERIOD_LEADERS: [StatCategory; 3] = [
+    StatCategory::Points,
+    StatCategory::Rebounds,
+    StatCategory::Assists,
+];
+
+/// Shown with the linescore while play is stopped between periods,
+/// a table of each team's leaders so far
+pub fn period_leaders(box_scores: &GameBoxScores) -> String {
+    let cells = |cells: Vec<String>| cells.join(&CELL_SEPARATOR.to_string());
+    let mut lines = vec![Line::small(cells(vec![
+        String::new(),
+        box_scores.home.abv.clone(),
+        box_scores.away.abv.clone(),
+    ]))];
+    for &category in PERIOD_LEADERS.iter() {
+        let leader = |team: &TeamBoxScore| {
+            team.box_score
+                .leader(category)
+                .map(|r| format!("{} {}", rank_names(r), category.format_value(r.value)))
+                .unwrap_or_default()
+        };
+        lines.push(Line::small(cells(vec![
+            category.label().to_string(),
+            leader(&box_scores.home),
+            leader(&box_scores.away),
+        ])));
+    }
+    lines.iter().map(Line::render).collect()
+}
+
+/// The top `n` players in `category`, tied players are joined
+/// with `/` and larger ties are collapsed into a count
+pub fn box_score(info: &BoxScore, category: StatCategory, n: usize) -> Option<String> {
+    let ranks = info.leaders(category, n);
+    if ranks.is_empty() {
+        return None;
+    }
+    let ranks: Vec<_> = ranks
+        .iter()
+        .map(|rank| format!("{} {}", rank_names(rank), category.format_value(rank.value)))
+        .collect();
+    Some(fit::render(
+        Line::medium(format!("{}: {}", category.label(), ranks.join(", "))),
+        FitPolicy::SINGLE_LINE,
+    ))
+}
+
+/// The rows of a box score table that fit on one screen
+/// under its header
+pub const BOX_SCORE_ROWS: usize = 5;
+
+/// The longest name in a box score table, any longer and the
+/// columns don't fit across the screen
+const BOX_SCORE_NAME_LEN: usize = 8;
+
+/// A box score table for `team` split into pages of
+/// [`BOX_SCORE_ROWS`] players, drawn as cells so the columns line up
+pub fn box_score_table(team: &TeamBoxScore) -> Vec<String> {
+    let players = team.table_order();
+    let row = |cells: &[String]| Line::small(cells.join(&CELL_SEPARATOR.to_string()));
+    players
+        .chunks(BOX_SCORE_ROWS)
+        .map(|page| {
+            let header: Vec<_> = [team.abv.as_str(), "MIN", "PTS", "REB", "AST", "FG"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+            let mut lines = vec![row(&header)];
+            for player in page {
+                let stats = &player.statistics;
+                lines.push(row(&[
+                    short_name(&player.player_name)
+                        .chars()
+                        .take(BOX_SCORE_NAME_LEN)
+                        .collect(),
+                    (stats.minutes_played() as u32).to_string(),
+                    stats.points.to_string(),
+                    stats.rebounds_total.to_string(),
+                    stats.assists.to_string(),
+                    format!("{}-{}", stats.field_goals_made, stats.field_goals_attempted),
+                ]));
+            }
+            lines.iter().map(Line::render).collect()
+        })
+        .collect()
+}
+
+pub fn bench_points(team: &TeamBoxScore) -> String {
+    let (starters, bench) = team.points_split();
+    Line::medium(format!("Starters {} | Bench {}", starters, bench)).render()
+}
+
+/// The players on `team` who were ruled out and why, `None` if
+/// everyone was available
+pub fn inactive(team: &TeamBoxScore) -> Option<String> {
+    let players: Vec<_> = team.inactive().collect();
+    if players.is_empty() {
+        return None;
+    }
+    let mut lines = vec![Line::medium(format!("{} inactive", team.abv))];
+    for player in players.iter().take(BOX_SCORE_ROWS) {
+        let reason = player
+            .not_playing_description
+            .clone()
+            .or_else(|| player.not_playing_reason.as_deref().map(not_playing_reason))
+            .unwrap_or_default();
+        lines.push(Line::small(format!("{} {}", player.player_name, reason)));
+    }
+    if players.len() > BOX_SCORE_ROWS {
+        lines.push(Line::small(format!(
+            "+{} more",
+            players.len() - BOX_SCORE_ROWS
+        )));
+    }
+    Some(lines.iter().map(Line::render).collect())
+}
+
+/// `INACTIVE_GLEAGUE_TWOWAY` to `gleague twoway`
+fn not_playing_reason(reason: &str) -> String {
+    let reason = reason
+        .strip_prefix("INACTIVE_")
+        .or_else(|| reason.strip_prefix("DNP_"))
+        .unwrap_or(reason);
+    reason.replace('_', " ").to_lowercase()
+}
+
+/// Both teams' top `top` ranks in `category` side by side, home
+/// first, one line per rank
+pub fn leader_comparison(
+    box_scores: &GameBoxScores,
+    category: StatCategory,
+    top: usize,
+) -> Option<String> {
+    let home = box_scores.home.box_score.leaders(category, top);
+    let away = box_scores.away.box_score.leaders(category, top);
+    if home.is_empty() && away.is_empty() {
+        return None;
+    }
+    let side = |rank: Option<&Rank>| {
+        rank.map(|r| {
+            let names: Vec<_> = r.names.iter().map(|n| short_name(n)).collect();
+            let names = if names.len() > 2 {
+                format!("{} tied", names.len())
+            } else {
+                names.join("/")
+            };
+            format!("{} {}", names, category.format_value(r.value))
+        })
+        .unwrap_or_else(|| "-".to_string())
+    };
+    let rows = home.len().max(away.len());
+    let lines = (0..rows)
+        .map(|i| {
+            let label = if i == 0 { category.abbreviation() } else { "" };
+            fit::render(
+                Line::small(format!(
+                    "{:<4} {} | {}",
+                    label,
+                    side(home.get(i)),
+                    side(away.get(i))
+                )),
+                FitPolicy::SMALL,
+            )
+        })
+        .collect();
+    Some(lines)
+}
+
+/// A followed player's points, rebounds and assists with
+/// their shooting
+pub fn player_line(player: &StatPlayer) -> String {
+    player_stats(player).render()
+}
+
+fn player_stats(player: &StatPlayer) -> Line {
+    let stats = &player.statistics;
+    let name = if player.family_name.is_empty() {
+        short_name(&player.player_name)
+    } else {
+        &player.family_name
+    };
+    Line::medium(format!(
+        "{} {}-{}-{} {}/{} FG",
+        name,
+        stats.points,
+        stats.rebounds_total,
+        stats.assists,
+        stats.field_goals_made,
+        stats.field_goals_attempted
+    ))
+}
+
+/// A followed player's stat line and the last play they were
+/// involved in, left unfitted, see [`fit::compose`]
+pub fn player(player: &StatPlayer, last_play: Option<&Action>) -> Vec<Line> {
+    let mut ret = vec![player_stats(player)];
+    if let Some(play) = last_play {
+        ret.push(Line::small(format!(
+            "Q{} {} {}",
+            play.quarter(),
+            play.clock(),
+            play.desc()
+        )));
+    }
+    ret
+}
+
+/// The roster's running total followed by each player's score,
+/// split into pages of [`BOX_SCORE_ROWS`] players
+pub fn fantasy(total: &FantasyTotal) -> Vec<String> {
+    let header = Line::medium(format!("Fantasy {:.1}", total.total));
+    if total.players.is_empty() {
+        return vec![header.render()];
+    }
+    total
+        .players
+        .chunks(BOX_SCORE_ROWS)
+        .map(|page| {
+            let mut ret = header.render();
+            for player in page {
+                let cells = [
+                    short_name(&player.name).to_string(),
+                    player.team.clone(),
+                    format!("{:.1}", player.score),
+                ];
+                ret += &Line::small(cells.join(&CELL_SEPARATOR.to_string())).render();
+            }
+            ret
+        })
+        .collect()
+}
+
+/// The two teams' totals side by side as a table of cells, home
+/// first, split into pages of [`BOX_SCORE_ROWS`] rows
+pub fn team_comparison(box_scores: &GameBoxScores) -> Vec<String> {
+    let (home, away) = (&box_scores.home.statistics, &box_scores.away.statistics);
+    let percent = |value: f32| format!("{:.1}", value * 100.0);
+    let rows = [
+        (
+            "FG%",
+            percent(home.totals.field_goals_percentage),
+            percent(away.totals.field_goals_percentage),
+        ),
+        (
+            "3P%",
+            percent(home.totals.three_pointers_percentage),
+            percent(away.totals.three_pointers_percentage),
+        ),
+        (
+            "Rebounds",
+            home.totals.rebounds_total.to_string(),
+            away.totals.rebounds_total.to_string(),
+        ),
+        (
+            "Turnovers",
+            home.turnovers().to_string(),
+            away.turnovers().to_string(),
+        ),
+        (
+            "Bench",
+            home.bench_points.to_string(),
+            away.bench_points.to_string(),
+        ),
+        (
+            "Fast Break",
+            home.totals.points_fast_break.to_string(),
+            away.totals.points_fast_break.to_string(),
+        ),
+        (
+            "Pts off TO",
+            home.points_from_turnovers.to_string(),
+            away.points_from_turnovers.to_string(),
+        ),
+        (
+            "Big Lead",
+            home.biggest_lead.to_string(),
+            away.biggest_lead.to_string(),
+        ),
+        (
+            "Lead Chg",
+            home.lead_changes.to_string(),
+            away.lead_changes.to_string(),
+        ),
+    ];
+    let row = |cells: [&str; 3]| Line::small(cells.join(&CELL_SEPARATOR.to_string()));
+    rows.chunks(BOX_SCORE_ROWS)
+        .map(|page| {
+            let mut lines = vec![row(["", &box_scores.home.abv, &box_scores.away.abv])];
+            for (label, home, away) in page {
+                lines.push(row([label, home, away]));
+            }
+            lines.iter().map(Line::render).collect()
+        })
+        .collect()
+}
 
-    Some(Line::medium(format!("{}: {} {}", name, stat.name, stat.value)).render())
+/// `A. Horford` to `Horford`
+fn short_name(name: &str) -> &str {
+    match name.split_once(". ") {
+        Some((initial, last)) if initial.len() <= 2 => last,
+        _ => name,
+    }
 }
 
-pub fn action(action: &Action) -> String {
+fn rank_names(rank: &Rank) -> String {
+    if rank.names.len() > 3 {
+        format!("{} tied", rank.names.len())
+    } else {
+        rank.names.join("/")
+    }
+}
+
+/// Left unfitted, whether it can wrap depends on what else is
+/// on the screen, see [`fit::compose`]
+pub fn action(action: &Action) -> Line {
     Line::medium(format!(
         "Q{quarter} {clock} {desc}",
         quarter = action.quarter(),
         clock = action.clock(),
         desc = action.desc(),
     ))
+}
+
+pub fn momentum(momentum: &Momentum) -> Option<Line> {
+    let line = if let Some(run) = momentum.run.as_ref() {
+        run.to_string()
+    } else if let Some(drought) = momentum.longest_drought() {
+        drought.to_string()
+    } else if momentum.lead_changes > 0 || momentum.ties > 0 {
+        format!(
+            "Lead changes {} Ties {}",
+            momentum.lead_changes, momentum.ties
+        )
+    } else {
+        return None;
+    };
+    Some(Line::small(line))
+}
+
+/// The team fouls, followed by the latest foul trouble alert while
+/// it is recent. The bonus is left to the clock line's indicators
+pub fn fouls(fouls: &FoulTracker, game: &Game) -> Vec<Line> {
+    let (home, away) = (&game.home.tri_code, &game.away.tri_code);
+    let mut ret = vec![Line::small(format!(
+        "Fouls {} {} {} {}",
+        home,
+        fouls.team_fouls(home),
+        away,
+        fouls.team_fouls(away)
+    ))];
+    if let Some(alert) = fouls.recent_alerts().first() {
+        ret.push(Line::small(alert));
+    }
+    ret
+}
+
+/// A milestone in reach over two or three short lines, with the
+/// time left when the player is still chasing it, left unfitted,
+/// see [`fit::compose`]
+pub fn milestone(milestone: &Milestone, game: &Game) -> Vec<Line> {
+    let mut ret = vec![
+        Line::small(milestone.headline()),
+        Line::small(milestone.detail()),
+    ];
+    if let Some(clock) = game.clock.filter(|_| !milestone.is_reached()) {
+        ret.push(Line::small(format!(
+            "{} left in Q{}",
+            clock,
+            game.period.as_number()
+        )));
+    }
+    ret
+}
+
+/// The players on the floor as a table of cells, home first, left
+/// unfitted, see [`fit::compose`]
+pub fn lineups(lineups: &LineupTracker) -> Vec<Line> {
+    let row = |home: &str, away: &str| [home, away].join(&CELL_SEPARATOR.to_string());
+    let mut ret = vec![Line::medium(row(&lineups.home.team, &lineups.away.team))];
+    let rows = lineups.home.players.len().max(lineups.away.players.len());
+    for i in 0..rows {
+        let home = lineups.home.players.get(i).map(|p| short_name(&p.name));
+        let away = lineups.away.players.get(i).map(|p| short_name(&p.name));
+        ret.push(Line::small(row(
+            home.unwrap_or_default(),
+            away.unwrap_or_default(),
+        )));
+    }
+    ret
+}
+
+pub fn best_lineup(lineup: &LineupPlusMinus) -> String {
+    Line::medium(format!(
+        "Best lineup {:+} in {}",
+        lineup.plus_minus(),
+        lineup.time
+    ))
     .render()
 }
 
@@ -94,7 +582,10 @@ pub fn next_up(game: &Game, home_team: &str) -> String {
     } else {
         format!("{} @ {}", game.away.tri_code, game.home.tri_code)
     };
-    [time, Line::medium(teams)].iter().map(Line::render).collect()
+    [time, Line::medium(teams)]
+        .iter()
+        .map(Line::render)
+        .collect()
 }
 
 fn next_game_time(time: chrono::DateTime<Utc>) -> Line {
@@ -129,9 +620,287 @@ pub fn scores_line(game: &Game) -> Line {
 }
 
 pub fn render_large_three_char_pair(lhs: &impl Display, rhs: &impl Display) -> Line {
-    Line::large(format!(
-        "{home:>3} {away:>3}",
-        home = lhs,
-        away = rhs
-    ))
+    Line::large(format!("{home:>3} {away:>3}", home = lhs, away = rhs))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        bitmap::{Renderer, HEIGHT, WIDTH},
+        test_util::{self, action, box_scores, made_shot, player, AWAY, HOME},
+    };
+
+    fn at_half() -> Game {
+        let team = |id: u32, tri: &str, q1: u32, q2: u32| {
+            json!({
+                "teamId": id,
+                "teamTricode": tri,
+                "wins": 0,
+                "losses": 0,
+                "score": q1 + q2,
+                "periods": [{"period": 1, "score": q1}, {"period": 2, "score": q2}],
+            })
+        };
+        test_util::game(json!({
+            "gameClock": "PT00M00.00S",
+            "gameStatusText": "Half",
+            "homeTeam": team(1, HOME, 30, 25),
+            "awayTeam": team(2, AWAY, 28, 22),
+        }))
+    }
+
+    #[test]
+    fn period_summary_splits() {
+        let plays = [
+            made_shot(2, "PT11M00.00S", HOME, 2, 0),
+            action(json!({
+                "actionNumber": 3,
+                "actionType": "2pt",
+                "period": 2,
+                "shotResult": "Made",
+            })),
+            action(json!({
+                "actionNumber": 4,
+                "actionType": "3pt",
+                "period": 2,
+                "shotResult": "Missed",
+            })),
+            action(json!({
+                "actionNumber": 5,
+                "actionType": "freethrow",
+                "subType": "1 of 2",
+                "period": 2,
+                "shotResult": "Made",
+            })),
+        ];
+        let lines = period_summary(&at_half(), &plays);
+        let texts: Vec<_> = lines.iter().map(Line::text).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Q2\tPTS\tFG\t3P\tFT",
+                "BOS\t25\t1/2\t0/1\t1/1",
+                "NYK\t22\t0/0\t0/0\t0/0",
+            ]
+        );
+    }
+
+    #[test]
+    fn period_summary_fits_under_the_score() {
+        let game = at_half();
+        let body = period_summary(&game, &[])
+            .into_iter()
+            .map(|line| (line, FitPolicy::SMALL))
+            .collect();
+        let screen = fit::compose(&super::game(&game), body, Vec::new());
+        let lines: Vec<_> = screen.lines().filter_map(Line::parse).collect();
+        assert_eq!(lines.len(), 6);
+        let layout = Renderer::shared().unwrap().layout(&lines, WIDTH as i32);
+        assert!(layout.bottom <= HEIGHT as i32);
+    }
+
+    /// Every line of `screen` is inside the display
+    fn assert_fits(screen: &str) {
+        let lines: Vec<_> = screen.lines().filter_map(Line::parse).collect();
+        let renderer = Renderer::shared().unwrap();
+        let layout = renderer.layout(&lines, WIDTH as i32);
+        assert!(layout.bottom <= HEIGHT as i32, "{}", screen);
+        for placed in layout.placed {
+            let right = placed.left + renderer.text_width(placed.text, placed.px);
+            assert!(placed.left >= 0 && right <= WIDTH as i32, "{:?}", placed);
+        }
+    }
+
+    #[test]
+    fn box_score_pages_fit() {
+        let stats = json!({
+            "minutes": "PT38M12.00S",
+            "points": 44,
+            "reboundsTotal": 18,
+            "assists": 12,
+            "fieldGoalsMade": 17,
+            "fieldGoalsAttempted": 30,
+        });
+        let home: Vec<_> = (1..=7)
+            .map(|id| player(id, "G. Antetokounmpo", id <= 5, stats.clone()))
+            .collect();
+        let box_scores = box_scores(home, Vec::new());
+        let pages = box_score_table(&box_scores.home);
+        assert_eq!(pages.len(), 2);
+        let rows: Vec<_> = pages[0].lines().collect();
+        assert_eq!(rows.len(), BOX_SCORE_ROWS + 1);
+        assert_eq!(rows[1], "0Antetoko\t38\t44\t18\t12\t17-30");
+        for page in &pages {
+            assert_fits(page);
+        }
+    }
+
+    #[test]
+    fn milestone_fits_under_the_score() {
+        let today = test_util::game(json!({}));
+        let alert = Milestone::TripleDouble {
+            team: HOME.to_string(),
+            name: "Antetokounmpo".to_string(),
+            points: 38,
+            rebounds: 14,
+            assists: 9,
+            reached: false,
+        };
+        let body = milestone(&alert, &today)
+            .into_iter()
+            .map(|line| (line, FitPolicy::SMALL))
+            .collect();
+        let head = super::game(&today);
+        let screen = fit::compose(&head, body, Vec::new());
+        let rows: Vec<_> = screen.lines().collect();
+        assert_eq!(rows.len(), head.lines().count() + 3);
+        assert_eq!(
+            rows[rows.len() - 3..],
+            [
+                "0Antetokounmpo 38-14-9",
+                "0triple-double watch",
+                "05:32 left in Q2",
+            ]
+        );
+        assert_fits(&screen);
+    }
+
+    #[test]
+    fn lineups_fit() {
+        let names = [
+            "G. Antetokounmpo",
+            "K. Middleton",
+            "D. Lillard",
+            "B. Lopez",
+            "M. Beasley",
+        ];
+        let home = names
+            .iter()
+            .zip(1..)
+            .map(|(name, id)| player(id, name, true, json!({})))
+            .collect();
+        let box_scores = box_scores(home, test_util::roster(11, 5));
+        let tracker = LineupTracker::from_starters(&box_scores);
+        let body: Vec<_> = lineups(&tracker)
+            .into_iter()
+            .map(|line| (line, FitPolicy::SMALL))
+            .collect();
+        let screen = fit::compose("", body, Vec::new());
+        let rows: Vec<_> = screen.lines().collect();
+        assert_eq!(rows.len(), 6);
+        assert_eq!(rows[0], "1BOS\tNYK");
+        assert_eq!(rows[1], "0Antetokounmpo\t11");
+        assert_fits(&screen);
+    }
+
+    fn scorer(id: u64, name: &str, points: u8) -> serde_json::Value {
+        player(id, name, true, json!({ "points": points }))
+    }
+
+    #[test]
+    fn leader_comparison_home_first() {
+        let box_scores = box_scores(
+            vec![scorer(1, "J. Tatum", 31), scorer(2, "J. Brown", 24)],
+            vec![scorer(11, "J. Brunson", 28)],
+        );
+        let line = leader_comparison(&box_scores, StatCategory::Points, 1).unwrap();
+        assert_eq!(line, "0PTS  Tatum 31 | Brunson 28\n");
+    }
+
+    #[test]
+    fn leader_comparison_top_n() {
+        let box_scores = box_scores(
+            vec![
+                scorer(1, "J. Tatum", 31),
+                scorer(2, "J. Brown", 24),
+                scorer(3, "D. White", 12),
+            ],
+            vec![scorer(11, "J. Brunson", 28)],
+        );
+        let lines = leader_comparison(&box_scores, StatCategory::Points, 2).unwrap();
+        assert_eq!(lines, "0PTS  Tatum 31 | Brunson 28\n0     Brown 24 | -\n");
+    }
+
+    #[test]
+    fn leader_comparison_ties_and_missing_sides() {
+        let tied = box_scores(
+            vec![
+                scorer(1, "J. Tatum", 20),
+                scorer(2, "J. Brown", 20),
+                scorer(3, "D. White", 20),
+            ],
+            vec![scorer(11, "J. Brunson", 20), scorer(12, "J. Randle", 20)],
+        );
+        let line = leader_comparison(&tied, StatCategory::Points, 1).unwrap();
+        assert_eq!(line, "0PTS  3 tied 20 | Brunson/Randle 20\n");
+        let steals = player(1, "J. Tatum", true, json!({ "steals": 2 }));
+        let home_only = box_scores(vec![steals], Vec::new());
+        let line = leader_comparison(&home_only, StatCategory::Steals, 1).unwrap();
+        assert_eq!(line, "0STL  Tatum 2 | -\n");
+        assert!(leader_comparison(&home_only, StatCategory::Blocks, 1).is_none());
+    }
+
+    #[test]
+    fn team_comparison_pages() {
+        let mut box_scores = box_scores(Vec::new(), Vec::new());
+        box_scores.home.statistics.totals.field_goals_percentage = 0.512;
+        box_scores.away.statistics.points_from_turnovers = 21;
+        let pages = team_comparison(&box_scores);
+        assert_eq!(pages.len(), 2);
+        let rows: Vec<_> = pages.iter().flat_map(|page| page.lines()).collect();
+        assert_eq!(rows[0], "0\tBOS\tNYK");
+        assert_eq!(rows[1], "0FG%\t51.2\t0.0");
+        assert!(rows.contains(&"0Pts off TO\t0\t21"));
+        for page in &pages {
+            assert_fits(page);
+        }
+    }
+
+    #[test]
+    fn fantasy_rows_are_cells() {
+        let fantasy_player = |name: &str, team: &str, score: f32| crate::fantasy::FantasyPlayer {
+            name: name.to_string(),
+            team: team.to_string(),
+            score,
+        };
+        let total = FantasyTotal {
+            players: vec![
+                fantasy_player("N. Jokic", "DEN", 71.3),
+                fantasy_player("J. Tatum", "BOS", 48.2),
+            ],
+            total: 119.5,
+        };
+        let pages = fantasy(&total);
+        assert_eq!(
+            pages,
+            vec!["1Fantasy 119.5\n0Jokic\tDEN\t71.3\n0Tatum\tBOS\t48.2\n"]
+        );
+        assert_fits(&pages[0]);
+    }
+
+    #[test]
+    fn period_leaders_per_team() {
+        let stats = |points: u8, rebounds: u8, assists: u8| {
+            json!({
+                "minutes": "PT20M00.00S",
+                "points": points,
+                "reboundsTotal": rebounds,
+                "assists": assists,
+            })
+        };
+        let home = vec![
+            player(1, "J. Tatum", true, stats(20, 8, 3)),
+            player(2, "J. Holiday", true, stats(6, 2, 7)),
+        ];
+        let away = vec![player(11, "J. Brunson", true, stats(18, 2, 5))];
+        let screen = period_leaders(&box_scores(home, away));
+        let lines: Vec<_> = screen.lines().filter_map(Line::parse).collect();
+        let texts: Vec<_> = lines.iter().map(Line::text).collect();
+        assert_eq!(texts[0], "\tBOS\tNYK");
+        assert_eq!(texts[1], "Points\tJ. Tatum 20\tJ. Brunson 18");
+        assert_eq!(texts[3], "Assists\tJ. Holiday 7\tJ. Brunson 5");
+    }
 }
@@ -0,0 +1,129 @@
+//! Inline fixtures shared by the unit tests
+
+use serde_json::{json, Value};
+
+use crate::{
+    action::Action,
+    box_score::{GameBoxScores, StatPlayer},
+    Game,
+};
+
+pub const HOME: &str = "BOS";
+pub const AWAY: &str = "NYK";
+
+/// Parse a play-by-play action between [`HOME`] and [`AWAY`],
+/// any field left out of `fields` gets a placeholder
+pub fn action(fields: Value) -> Action {
+    let mut value = json!({
+        "actionNumber": 1,
+        "actionType": "period",
+        "subType": "start",
+        "period": 1,
+        "clock": "PT12M00.00S",
+        "scoreHome": "0",
+        "scoreAway": "0",
+        "teamTricode": HOME,
+        "playerNameI": "J. Tatum",
+    });
+    for (key, field) in fields.as_object().expect("fields must be an object") {
+        value[key] = field.clone();
+    }
+    Action::try_from_obj(value.as_object().unwrap().clone(), HOME, AWAY)
+        .unwrap_or_else(|| panic!("invalid action {}", value))
+}
+
+/// A made field goal by `team` at `clock` in the first period that
+/// leaves the score at `home`-`away`
+pub fn made_shot(number: i64, clock: &str, team: &str, home: u16, away: u16) -> Action {
+    action(json!({
+        "actionNumber": number,
+        "actionType": "2pt",
+        "subType": "Layup",
+        "shotResult": "Made",
+        "clock": clock,
+        "teamTricode": team,
+        "scoreHome": home.to_string(),
+        "scoreAway": away.to_string(),
+    }))
+}
+
+/// `person_id` on [`HOME`] subbing `kind` (`in` or `out`)
+pub fn substitution(number: i64, period: u8, clock: &str, person_id: u64, kind: &str) -> Action {
+    action(json!({
+        "actionNumber": number,
+        "actionType": "substitution",
+        "subType": kind,
+        "period": period,
+        "clock": clock,
+        "personId": person_id,
+    }))
+}
+
+/// A box score player with `statistics` in the feed's format
+pub fn player(person_id: u64, name: &str, starter: bool, statistics: Value) -> Value {
+    json!({
+        "personId": person_id,
+        "nameI": name,
+        "starter": if starter { "1" } else { "0" },
+        "statistics": statistics,
+    })
+}
+
+/// A parsed [`player`] off the bench
+pub fn stat_player(person_id: u64, name: &str, statistics: Value) -> StatPlayer {
+    serde_json::from_value(player(person_id, name, false, statistics)).unwrap()
+}
+
+/// `count` players with ids from `first_id` up and no stats,
+/// the first five start
+pub fn roster(first_id: u64, count: u64) -> Vec<Value> {
+    (first_id..first_id + count)
+        .map(|id| player(id, &format!("P. {}", id), id < first_id + 5, json!({})))
+        .collect()
+}
+
+/// A box score for [`HOME`] against [`AWAY`]
+pub fn box_scores(home: Vec<Value>, away: Vec<Value>) -> GameBoxScores {
+    let json = json!({
+        "game": {
+            "gameId": "0022300001",
+            "homeTeam": {"teamTricode": HOME, "players": home},
+            "awayTeam": {"teamTricode": AWAY, "players": away},
+        }
+    });
+    GameBoxScores::from_json(&json.to_string()).unwrap()
+}
+
+/// A live scoreboard game of [`HOME`] against [`AWAY`] in the
+/// second period as the feed sends it, any field left out of
+/// `fields` gets a placeholder
+pub fn game_json(fields: Value) -> Value {
+    let team = |id: u32, tri: &str| {
+        json!({
+            "teamId": id,
+            "teamTricode": tri,
+            "wins": 0,
+            "losses": 0,
+            "score": 0,
+            "periods": [],
+        })
+    };
+    let mut value = json!({
+        "gameId": "0022300001",
+        "gameTimeUTC": "2023-10-24T23:30:00Z",
+        "gameClock": "PT05M32.00S",
+        "gameStatusText": "Q2 5:32",
+        "period": 2,
+        "homeTeam": team(1, HOME),
+        "awayTeam": team(2, AWAY),
+    });
+    for (key, field) in fields.as_object().expect("fields must be an object") {
+        value[key] = field.clone();
+    }
+    value
+}
+
+/// A parsed [`game_json`]
+pub fn game(fields: Value) -> Game {
+    serde_json::from_value(game_json(fields)).unwrap()
+}